@@ -1,7 +1,60 @@
-use crate::features::extract_features;
+use crate::features::{FeatureExtractor, FeatureExtractorConfig};
 use crate::isolation_forest::IsolationForest;
 use crate::parser::NetworkEvent;
-use crate::reporter::AnomalyReport;
+use crate::reporter::{AnomalyReport, Severity, TopAnomalies};
+use rand::Rng;
+use std::path::PathBuf;
+
+/// How `Detector` decides whether a score counts as anomalous.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThresholdMode {
+    /// Use `DetectorConfig::threshold` directly.
+    Fixed,
+    /// Derive a cutoff from the buffered score distribution using Tukey's
+    /// outlier fences, recomputed on every retrain.
+    Adaptive,
+}
+
+/// The computed Tukey fences for a batch of scores: values above `mild`
+/// are "mild" outliers, values above `severe` are "severe" outliers.
+#[derive(Debug, Clone, Copy)]
+struct Fences {
+    mild: f64,
+    severe: f64,
+}
+
+/// The `p`-th percentile of `sorted` via linear interpolation between
+/// closest ranks (`p` in `[0.0, 1.0]`).
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    if sorted.len() == 1 {
+        return sorted[0];
+    }
+
+    let rank = p * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    if lower == upper {
+        sorted[lower]
+    } else {
+        let frac = rank - lower as f64;
+        sorted[lower] + frac * (sorted[upper] - sorted[lower])
+    }
+}
+
+/// How `Detector` maintains the retraining buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferMode {
+    /// Keep a sliding window of the most recent feature vectors (it grows to
+    /// `2 * buffer_size`, then is trimmed back to the latest `buffer_size`),
+    /// biasing every retrain toward the tail of the stream.
+    Windowed,
+    /// Keep a uniform random sample of every feature vector seen so far
+    /// (Vitter's Algorithm R), so retraining reflects the whole session.
+    Reservoir,
+}
 
 /// Configuration for the anomaly detector.
 pub struct DetectorConfig {
@@ -9,6 +62,28 @@ pub struct DetectorConfig {
     pub buffer_size: usize,
     pub threshold: f64,
     pub retrain_interval: usize,
+    /// Isolation forest split mode: `0` is axis-parallel (the original
+    /// behavior), higher values extend splits toward random hyperplanes.
+    pub extension_level: usize,
+    pub buffer_mode: BufferMode,
+    pub threshold_mode: ThresholdMode,
+    /// Tukey fence multiplier for a "mild" outlier (used when
+    /// `threshold_mode` is `Adaptive`).
+    pub mild_fence_k: f64,
+    /// Tukey fence multiplier for a "severe" outlier.
+    pub severe_fence_k: f64,
+    /// Where to persist the trained forest between runs. When present and
+    /// the file already exists, `Detector::new` loads it and skips the
+    /// buffering/warm-up phase; every `train()` call re-saves it.
+    pub model_path: Option<PathBuf>,
+    /// How many of the highest-scoring anomalies to retain for the
+    /// end-of-session top-anomalies report.
+    pub top_k: usize,
+    /// Sketch sizing for the feature extractor's Count-Min Sketch and
+    /// HyperLogLog. Decayed every `retrain_interval` events so estimates
+    /// track concept drift instead of accumulating for the life of the
+    /// stream.
+    pub feature_config: FeatureExtractorConfig,
 }
 
 impl Default for DetectorConfig {
@@ -18,6 +93,14 @@ impl Default for DetectorConfig {
             buffer_size: 256,
             threshold: 0.65,
             retrain_interval: 1000,
+            extension_level: 0,
+            buffer_mode: BufferMode::Windowed,
+            threshold_mode: ThresholdMode::Fixed,
+            mild_fence_k: 1.5,
+            severe_fence_k: 3.0,
+            model_path: None,
+            top_k: 10,
+            feature_config: FeatureExtractorConfig::default(),
         }
     }
 }
@@ -27,35 +110,62 @@ impl Default for DetectorConfig {
 pub struct Detector {
     config: DetectorConfig,
     forest: Option<IsolationForest>,
+    features: FeatureExtractor,
     buffer: Vec<Vec<f64>>,
+    /// Count of feature vectors offered to the buffer, used as `k` in
+    /// Algorithm R when `buffer_mode` is `Reservoir`.
+    seen: usize,
     events_since_train: usize,
     total_events: usize,
     total_anomalies: usize,
+    /// Tukey fences computed from the buffered score distribution, present
+    /// once trained when `threshold_mode` is `Adaptive`.
+    fences: Option<Fences>,
+    /// The highest-scoring anomalies seen so far, for the end-of-session
+    /// top-anomalies report.
+    top_anomalies: TopAnomalies,
 }
 
 impl Detector {
     pub fn new(config: DetectorConfig) -> Self {
+        let forest = config
+            .model_path
+            .as_ref()
+            .and_then(|path| IsolationForest::load(path).ok());
+        let top_anomalies = TopAnomalies::new(config.top_k);
+        let features = FeatureExtractor::with_config(config.feature_config);
+
         Self {
             config,
-            forest: None,
+            forest,
+            features,
             buffer: Vec::new(),
+            seen: 0,
             events_since_train: 0,
             total_events: 0,
             total_anomalies: 0,
+            fences: None,
+            top_anomalies,
         }
     }
 
+    /// The highest-scoring anomalies tracked so far, for an end-of-session
+    /// "worst offenders" summary.
+    pub fn top_anomalies(&self) -> &TopAnomalies {
+        &self.top_anomalies
+    }
+
     /// Process a single network event.
     ///
     /// Returns `Some(AnomalyReport)` if the event is anomalous,
     /// `None` if normal or still buffering.
     pub fn process(&mut self, event: &NetworkEvent) -> Option<AnomalyReport> {
-        let features = extract_features(event);
+        let features = self.features.extract(event);
         self.total_events += 1;
+        self.push_to_buffer(features.clone());
 
         // Buffering phase: collect initial samples for training
         if self.forest.is_none() {
-            self.buffer.push(features);
             if self.buffer.len() >= self.config.buffer_size {
                 self.train();
             }
@@ -68,36 +178,133 @@ impl Detector {
         let score = self.forest.as_ref().unwrap().score(&features);
 
         self.events_since_train += 1;
-
-        // Buffer for periodic retraining
-        self.buffer.push(features);
-        if self.buffer.len() > self.config.buffer_size * 2 {
-            let drain_count = self.buffer.len() - self.config.buffer_size;
-            self.buffer.drain(0..drain_count);
-        }
         if self.events_since_train >= self.config.retrain_interval {
             self.train();
         }
 
-        if score >= self.config.threshold {
+        let effective_threshold = match self.config.threshold_mode {
+            ThresholdMode::Fixed => self.config.threshold,
+            ThresholdMode::Adaptive => self
+                .fences
+                .map(|f| f.mild)
+                .unwrap_or(self.config.threshold),
+        };
+
+        if score >= effective_threshold {
             self.total_anomalies += 1;
-            Some(AnomalyReport {
+            let report = AnomalyReport {
                 event: event.clone(),
                 score,
                 event_number: self.total_events,
-            })
+                severity: self.classify_severity(score),
+            };
+            self.top_anomalies.offer(report.clone());
+            Some(report)
         } else {
             None
         }
     }
 
+    /// Classify how anomalous `score` is: in `Adaptive` mode this compares
+    /// against the current Tukey fences, otherwise it falls back to the
+    /// original fixed 0.7/0.8 score bands.
+    fn classify_severity(&self, score: f64) -> Severity {
+        match self.config.threshold_mode {
+            ThresholdMode::Adaptive => match self.fences {
+                Some(fences) if score >= fences.severe => Severity::High,
+                Some(fences) if score >= fences.mild => Severity::Medium,
+                _ => Severity::Low,
+            },
+            ThresholdMode::Fixed => {
+                if score >= 0.8 {
+                    Severity::High
+                } else if score >= 0.7 {
+                    Severity::Medium
+                } else {
+                    Severity::Low
+                }
+            }
+        }
+    }
+
+    /// Admit a feature vector into the retraining buffer according to
+    /// `buffer_mode`.
+    fn push_to_buffer(&mut self, features: Vec<f64>) {
+        match self.config.buffer_mode {
+            BufferMode::Windowed => {
+                self.buffer.push(features);
+                if self.buffer.len() > self.config.buffer_size * 2 {
+                    let drain_count = self.buffer.len() - self.config.buffer_size;
+                    self.buffer.drain(0..drain_count);
+                }
+            }
+            BufferMode::Reservoir => {
+                self.seen += 1;
+                if self.buffer.len() < self.config.buffer_size {
+                    self.buffer.push(features);
+                } else {
+                    let j = rand::thread_rng().gen_range(0..self.seen);
+                    if j < self.config.buffer_size {
+                        self.buffer[j] = features;
+                    }
+                }
+            }
+        }
+    }
+
     fn train(&mut self) {
-        self.forest = Some(IsolationForest::fit(
+        let forest = IsolationForest::fit_extended(
             &self.buffer,
             self.config.n_trees,
             self.config.buffer_size,
-        ));
+            self.config.extension_level,
+        );
+
+        if self.config.threshold_mode == ThresholdMode::Adaptive {
+            self.fences = Some(Self::compute_fences(
+                &forest,
+                &self.buffer,
+                self.config.mild_fence_k,
+                self.config.severe_fence_k,
+            ));
+        }
+
+        if let Some(path) = &self.config.model_path {
+            if let Err(err) = forest.save(path) {
+                eprintln!("warning: failed to persist isolation forest to {path:?}: {err}");
+            }
+        }
+
+        self.forest = Some(forest);
         self.events_since_train = 0;
+
+        // Retraining is also the decay point for the feature sketches. This
+        // runs *after* the forest is fit on `self.buffer`, so the vectors it
+        // just trained on and the decay that's about to happen don't fight
+        // each other: the forest keeps seeing the values it was trained on,
+        // and the decay only affects sketch state going forward.
+        self.features.decay();
+    }
+
+    /// Score every buffered point and derive Tukey outlier fences from the
+    /// resulting distribution.
+    fn compute_fences(
+        forest: &IsolationForest,
+        buffer: &[Vec<f64>],
+        mild_k: f64,
+        severe_k: f64,
+    ) -> Fences {
+        let mut scores: Vec<f64> = buffer.iter().map(|point| forest.score(point)).collect();
+        scores.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let q1 = percentile(&scores, 0.25);
+        let q3 = percentile(&scores, 0.75);
+        let iqr = q3 - q1;
+
+        Fences {
+            mild: q3 + mild_k * iqr,
+            severe: q3 + severe_k * iqr,
+        }
     }
 
     pub fn total_events(&self) -> usize {
@@ -112,3 +319,85 @@ impl Detector {
         self.forest.is_some()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile_linear_interpolation() {
+        let sorted = vec![1.0, 2.0, 3.0, 4.0];
+        assert_eq!(percentile(&sorted, 0.0), 1.0);
+        assert_eq!(percentile(&sorted, 1.0), 4.0);
+        assert_eq!(percentile(&sorted, 0.5), 2.5);
+    }
+
+    #[test]
+    fn test_adaptive_threshold_trains_and_flags_outliers() {
+        let mut detector = Detector::new(DetectorConfig {
+            buffer_size: 50,
+            n_trees: 20,
+            threshold_mode: ThresholdMode::Adaptive,
+            ..Default::default()
+        });
+
+        for _ in 0..50 {
+            detector.push_to_buffer(vec![0.5, 0.5, 0.5]);
+        }
+        detector.train();
+
+        assert!(detector.fences.is_some());
+        // A maximal anomaly score should clear the mild fence.
+        let severity = detector.classify_severity(1.0);
+        assert_ne!(severity, Severity::Low);
+    }
+
+    #[test]
+    fn test_reservoir_never_exceeds_buffer_size() {
+        let mut detector = Detector::new(DetectorConfig {
+            buffer_size: 10,
+            buffer_mode: BufferMode::Reservoir,
+            ..Default::default()
+        });
+
+        for i in 0..500 {
+            detector.push_to_buffer(vec![i as f64]);
+        }
+
+        assert_eq!(detector.buffer.len(), 10);
+    }
+
+    #[test]
+    fn test_reservoir_keeps_early_items_with_expected_probability() {
+        // Feed N items through a reservoir of size K many times and check
+        // that item 0 survives roughly K/N of the time, as Algorithm R
+        // guarantees for a uniform sample.
+        let n = 50;
+        let k = 5;
+        let trials = 2000;
+        let mut survived = 0;
+
+        for _ in 0..trials {
+            let mut detector = Detector::new(DetectorConfig {
+                buffer_size: k,
+                buffer_mode: BufferMode::Reservoir,
+                ..Default::default()
+            });
+            for i in 0..n {
+                detector.push_to_buffer(vec![i as f64]);
+            }
+            if detector.buffer.contains(&vec![0.0]) {
+                survived += 1;
+            }
+        }
+
+        let observed = survived as f64 / trials as f64;
+        let expected = k as f64 / n as f64;
+        assert!(
+            (observed - expected).abs() < 0.05,
+            "observed survival rate {} too far from expected {}",
+            observed,
+            expected
+        );
+    }
+}
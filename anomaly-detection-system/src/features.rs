@@ -0,0 +1,328 @@
+use crate::parser::NetworkEvent;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// A probabilistic frequency counter with one-sided error: an estimated
+/// count is never below the true count, but hash collisions across
+/// independent rows can inflate it. Used here to approximate how often a
+/// `(src_ip, dst_port)` pair has been seen without storing every key.
+struct CountMinSketch {
+    width: usize,
+    counters: Vec<Vec<u32>>,
+    seeds: Vec<u64>,
+}
+
+impl CountMinSketch {
+    fn new(width: usize, depth: usize) -> Self {
+        Self {
+            width,
+            counters: vec![vec![0; width]; depth],
+            seeds: (0..depth).map(|i| 0x9E3779B97F4A7C15 ^ (i as u64)).collect(),
+        }
+    }
+
+    fn bucket(&self, key: &str, seed: u64) -> usize {
+        let mut hasher = DefaultHasher::new();
+        seed.hash(&mut hasher);
+        key.hash(&mut hasher);
+        (hasher.finish() % self.width as u64) as usize
+    }
+
+    fn increment(&mut self, key: &str) {
+        for row in 0..self.seeds.len() {
+            let bucket = self.bucket(key, self.seeds[row]);
+            self.counters[row][bucket] = self.counters[row][bucket].saturating_add(1);
+        }
+    }
+
+    /// The minimum count across all rows' buckets for `key`: the sketch's
+    /// best (smallest, hence least collision-inflated) estimate.
+    fn estimate(&self, key: &str) -> u32 {
+        (0..self.seeds.len())
+            .map(|row| self.counters[row][self.bucket(key, self.seeds[row])])
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Halve every counter in place. Unlike zeroing the sketch outright,
+    /// this fades old observations gradually, so a forest retrained right
+    /// after a decay still sees counts within the same order of magnitude
+    /// it was just trained on.
+    fn decay(&mut self) {
+        for row in &mut self.counters {
+            for counter in row.iter_mut() {
+                *counter /= 2;
+            }
+        }
+    }
+}
+
+/// A probabilistic cardinality estimator (HyperLogLog). Used here to
+/// approximate the number of distinct `(dst_ip, dst_port)` pairs a source IP
+/// has contacted, a cheap proxy for fan-out/scanning behavior that would
+/// otherwise require storing every distinct pair ever seen.
+struct HyperLogLog {
+    registers: Vec<u8>,
+    precision: u32,
+}
+
+impl HyperLogLog {
+    fn new(precision: u32) -> Self {
+        Self {
+            registers: vec![0; 1 << precision],
+            precision,
+        }
+    }
+
+    fn hash(key: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn insert(&mut self, key: &str) {
+        let hash = Self::hash(key);
+        let bucket = (hash >> (64 - self.precision)) as usize;
+        let rest = (hash << self.precision) | (1 << (self.precision - 1));
+        let rho = rest.leading_zeros() as u8 + 1;
+        self.registers[bucket] = self.registers[bucket].max(rho);
+    }
+
+    /// The estimated number of distinct keys inserted so far.
+    fn estimate(&self) -> f64 {
+        let m = self.registers.len() as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+
+        let sum: f64 = self.registers.iter().map(|&r| 2f64.powi(-(r as i32))).sum();
+        let raw = alpha * m * m / sum;
+
+        // Small-range correction: fall back to linear counting when many
+        // registers are still empty.
+        let zeros = self.registers.iter().filter(|&&r| r == 0).count();
+        if raw <= 2.5 * m && zeros > 0 {
+            m * (m / zeros as f64).ln()
+        } else {
+            raw
+        }
+    }
+
+    /// Decay every register by one step (registers are themselves a log2
+    /// scale, so this roughly halves the estimate rather than zeroing it
+    /// outright), so a source's fan-out estimate cools off gradually.
+    fn decay(&mut self) {
+        for register in &mut self.registers {
+            *register = register.saturating_sub(1);
+        }
+    }
+
+    /// Whether every register has decayed back to empty, meaning this
+    /// source's fan-out estimate has fully faded and the entry can be
+    /// dropped instead of tracked forever.
+    fn is_stale(&self) -> bool {
+        self.registers.iter().all(|&r| r == 0)
+    }
+}
+
+/// Configuration for the sketches backing `FeatureExtractor`.
+#[derive(Debug, Clone, Copy)]
+pub struct FeatureExtractorConfig {
+    /// Number of counters per Count-Min Sketch row. Wider rows cut
+    /// collision-driven overestimation at the cost of more memory.
+    pub sketch_width: usize,
+    /// Number of Count-Min Sketch rows. More rows tighten the `estimate`
+    /// bound but each adds a full hash pass per `increment`.
+    pub sketch_depth: usize,
+    /// HyperLogLog precision (`2^precision` registers per source IP).
+    pub hll_precision: u32,
+}
+
+impl Default for FeatureExtractorConfig {
+    fn default() -> Self {
+        Self {
+            sketch_width: 2048,
+            sketch_depth: 4,
+            hll_precision: 10,
+        }
+    }
+}
+
+/// Derives a numeric feature vector from `NetworkEvent`s, maintaining the
+/// sketches needed for cardinality- and frequency-based features across the
+/// whole stream.
+pub struct FeatureExtractor {
+    config: FeatureExtractorConfig,
+    /// Estimated frequency of each `(src_ip, dst_port)` pair seen so far.
+    dst_port_freq: CountMinSketch,
+    /// Estimated count of distinct `(dst_ip, dst_port)` pairs contacted, per
+    /// source IP.
+    src_fanout: HashMap<String, HyperLogLog>,
+}
+
+impl FeatureExtractor {
+    pub fn new() -> Self {
+        Self::with_config(FeatureExtractorConfig::default())
+    }
+
+    pub fn with_config(config: FeatureExtractorConfig) -> Self {
+        Self {
+            dst_port_freq: CountMinSketch::new(config.sketch_width, config.sketch_depth),
+            src_fanout: HashMap::new(),
+            config,
+        }
+    }
+
+    /// Decay accumulated sketch state instead of letting it grow forever.
+    ///
+    /// `Detector` calls this every `retrain_interval` events, alongside
+    /// retraining the forest, so frequency and fan-out estimates track
+    /// concept drift. This fades counts gradually rather than zeroing them
+    /// outright: a hard reset would mean every feature extracted right after
+    /// a retrain collapses toward zero while the forest was just trained on
+    /// much larger values, a systematic skew between training and scoring.
+    /// Per-source `HyperLogLog`s that have fully decayed are dropped, so
+    /// `src_fanout` stays bounded instead of keeping one entry per source IP
+    /// for the life of the process.
+    pub fn decay(&mut self) {
+        self.dst_port_freq.decay();
+        self.src_fanout.retain(|_, fanout| {
+            fanout.decay();
+            !fanout.is_stale()
+        });
+    }
+
+    /// Extract features for `event`, updating the sketches as a side effect.
+    pub fn extract(&mut self, event: &NetworkEvent) -> Vec<f64> {
+        let port_key = format!("{}:{}", event.src_ip, event.dst_port);
+        self.dst_port_freq.increment(&port_key);
+        let port_freq = self.dst_port_freq.estimate(&port_key) as f64;
+
+        let precision = self.config.hll_precision;
+        let fanout = self
+            .src_fanout
+            .entry(event.src_ip.clone())
+            .or_insert_with(|| HyperLogLog::new(precision));
+        fanout.insert(&format!("{}:{}", event.dst_ip, event.dst_port));
+        let distinct_dsts = fanout.estimate();
+
+        vec![
+            event.bytes as f64,
+            event.duration,
+            event.src_port as f64,
+            event.dst_port as f64,
+            event.protocol.as_f64(),
+            port_freq,
+            distinct_dsts,
+        ]
+    }
+}
+
+impl Default for FeatureExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_min_sketch_never_underestimates() {
+        let mut cms = CountMinSketch::new(64, 4);
+        for _ in 0..5 {
+            cms.increment("10.0.0.1:443");
+        }
+        cms.increment("10.0.0.2:80");
+
+        assert!(cms.estimate("10.0.0.1:443") >= 5);
+        assert!(cms.estimate("10.0.0.2:80") >= 1);
+    }
+
+    #[test]
+    fn test_hyperloglog_estimates_distinct_count_within_tolerance() {
+        let mut hll = HyperLogLog::new(10);
+        for i in 0..1000 {
+            hll.insert(&format!("192.168.{}.{}", i / 256, i % 256));
+        }
+
+        let estimate = hll.estimate();
+        assert!(
+            (estimate - 1000.0).abs() / 1000.0 < 0.1,
+            "estimate {estimate} too far from true cardinality 1000"
+        );
+    }
+
+    #[test]
+    fn test_feature_extractor_tracks_distinct_destinations_per_source() {
+        use crate::parser::Protocol;
+        use chrono::NaiveDate;
+
+        let mut extractor = FeatureExtractor::new();
+        let make_event = |src: &str, dst: &str| NetworkEvent {
+            timestamp: NaiveDate::from_ymd_opt(2024, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+            src_ip: src.to_string(),
+            src_port: 1234,
+            dst_ip: dst.to_string(),
+            dst_port: 443,
+            protocol: Protocol::Tcp,
+            bytes: 100,
+            duration: 0.1,
+        };
+
+        let first = extractor.extract(&make_event("10.0.0.1", "8.8.8.8"));
+        let second = extractor.extract(&make_event("10.0.0.1", "1.1.1.1"));
+
+        // Same source contacting a new destination should raise the
+        // fan-out feature (last element).
+        assert!(second.last().unwrap() > first.last().unwrap());
+    }
+
+    #[test]
+    fn test_count_min_sketch_decay_halves_without_zeroing() {
+        let mut cms = CountMinSketch::new(64, 4);
+        for _ in 0..20 {
+            cms.increment("10.0.0.1:443");
+        }
+
+        cms.decay();
+
+        let decayed = cms.estimate("10.0.0.1:443");
+        assert!(decayed > 0, "decay should fade, not zero, an active key");
+        assert!(decayed < 20, "decay should reduce the estimate");
+    }
+
+    #[test]
+    fn test_feature_extractor_decay_drops_fully_faded_sources() {
+        use crate::parser::Protocol;
+        use chrono::NaiveDate;
+
+        let mut extractor = FeatureExtractor::new();
+        let event = NetworkEvent {
+            timestamp: NaiveDate::from_ymd_opt(2024, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+            src_ip: "10.0.0.1".to_string(),
+            src_port: 1234,
+            dst_ip: "8.8.8.8".to_string(),
+            dst_port: 443,
+            protocol: Protocol::Tcp,
+            bytes: 100,
+            duration: 0.1,
+        };
+        extractor.extract(&event);
+
+        assert!(!extractor.src_fanout.is_empty());
+        for _ in 0..256 {
+            extractor.decay();
+        }
+        assert!(
+            extractor.src_fanout.is_empty(),
+            "a source that never reappears should eventually be pruned"
+        );
+    }
+}
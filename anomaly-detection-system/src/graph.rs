@@ -0,0 +1,212 @@
+use crate::parser::{NetworkEvent, Protocol};
+use std::collections::HashMap;
+
+/// Aggregated traffic statistics for a single `(src_ip, dst_ip)` pair.
+#[derive(Debug, Clone)]
+pub struct EdgeStats {
+    pub total_bytes: u64,
+    pub flow_count: usize,
+    protocol_counts: HashMap<Protocol, usize>,
+}
+
+impl EdgeStats {
+    fn new() -> Self {
+        Self {
+            total_bytes: 0,
+            flow_count: 0,
+            protocol_counts: HashMap::new(),
+        }
+    }
+
+    fn record(&mut self, event: &NetworkEvent) {
+        self.total_bytes += event.bytes;
+        self.flow_count += 1;
+        *self.protocol_counts.entry(event.protocol).or_insert(0) += 1;
+    }
+
+    /// The most frequently observed protocol on this edge.
+    fn dominant_protocol(&self) -> Protocol {
+        self.protocol_counts
+            .iter()
+            .max_by_key(|(_, count)| **count)
+            .map(|(protocol, _)| *protocol)
+            .unwrap_or(Protocol::Other)
+    }
+}
+
+/// Builds a directed communication graph from parsed `NetworkEvent`s,
+/// aggregating per-`(src_ip, dst_ip)` traffic into `EdgeStats`.
+pub struct GraphBuilder {
+    edges: HashMap<(String, String), EdgeStats>,
+}
+
+impl GraphBuilder {
+    pub fn new() -> Self {
+        Self {
+            edges: HashMap::new(),
+        }
+    }
+
+    /// Aggregate a batch of events into the graph.
+    pub fn build(events: &[NetworkEvent]) -> Self {
+        let mut builder = Self::new();
+        for event in events {
+            builder.add_event(event);
+        }
+        builder
+    }
+
+    pub fn add_event(&mut self, event: &NetworkEvent) {
+        let key = (event.src_ip.clone(), event.dst_ip.clone());
+        self.edges.entry(key).or_insert_with(EdgeStats::new).record(event);
+    }
+
+    fn nodes(&self) -> Vec<String> {
+        let mut ips: Vec<String> = self
+            .edges
+            .keys()
+            .flat_map(|(src, dst)| [src.clone(), dst.clone()])
+            .collect();
+        ips.sort();
+        ips.dedup();
+        ips
+    }
+
+    /// Render the aggregated graph as a Graphviz DOT `digraph`.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph network {\n");
+
+        for ip in self.nodes() {
+            let color = if is_private_ip(&ip) { "lightblue" } else { "lightgray" };
+            dot.push_str(&format!(
+                "  \"{}\" [style=filled, fillcolor={}];\n",
+                ip, color
+            ));
+        }
+
+        let max_bytes = self
+            .edges
+            .values()
+            .map(|stats| stats.total_bytes)
+            .max()
+            .unwrap_or(1)
+            .max(1);
+
+        for ((src, dst), stats) in &self.edges {
+            let penwidth = 1.0 + 4.0 * (stats.total_bytes as f64 / max_bytes as f64);
+            dot.push_str(&format!(
+                "  \"{}\" -> \"{}\" [label=\"{} ({} flows, {})\", penwidth={:.2}];\n",
+                src,
+                dst,
+                format_bytes(stats.total_bytes),
+                stats.flow_count,
+                stats.dominant_protocol(),
+                penwidth,
+            ));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl Default for GraphBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether an IPv4 address falls in one of the RFC 1918 private ranges.
+fn is_private_ip(ip: &str) -> bool {
+    let octets: Vec<&str> = ip.split('.').collect();
+    if octets.len() != 4 {
+        return false;
+    }
+    let first: u32 = match octets[0].parse() {
+        Ok(v) => v,
+        Err(_) => return false,
+    };
+    let second: u32 = match octets[1].parse() {
+        Ok(v) => v,
+        Err(_) => return false,
+    };
+
+    first == 10
+        || (first == 172 && (16..=31).contains(&second))
+        || (first == 192 && second == 168)
+}
+
+/// Render a byte count as a short human-readable string (e.g. `1.5KB`).
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{}{}", bytes, UNITS[unit])
+    } else {
+        format!("{:.1}{}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+
+    fn event(src: &str, dst: &str, bytes: u64, protocol: Protocol) -> NetworkEvent {
+        NetworkEvent {
+            timestamp: NaiveDateTime::parse_from_str("2024-01-15T10:30:00", "%Y-%m-%dT%H:%M:%S")
+                .unwrap(),
+            src_ip: src.to_string(),
+            src_port: 1234,
+            dst_ip: dst.to_string(),
+            dst_port: 443,
+            protocol,
+            bytes,
+            duration: 0.05,
+        }
+    }
+
+    #[test]
+    fn test_aggregates_edge_stats() {
+        let events = vec![
+            event("192.168.1.10", "10.0.0.1", 1000, Protocol::Tcp),
+            event("192.168.1.10", "10.0.0.1", 2000, Protocol::Tcp),
+        ];
+        let graph = GraphBuilder::build(&events);
+        let stats = &graph.edges[&("192.168.1.10".to_string(), "10.0.0.1".to_string())];
+        assert_eq!(stats.total_bytes, 3000);
+        assert_eq!(stats.flow_count, 2);
+        assert_eq!(stats.dominant_protocol(), Protocol::Tcp);
+    }
+
+    #[test]
+    fn test_to_dot_contains_nodes_and_edges() {
+        let events = vec![event("192.168.1.10", "8.8.8.8", 500, Protocol::Udp)];
+        let dot = GraphBuilder::build(&events).to_dot();
+        assert!(dot.starts_with("digraph network {"));
+        assert!(dot.contains("\"192.168.1.10\""));
+        assert!(dot.contains("\"8.8.8.8\""));
+        assert!(dot.contains("->"));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn test_is_private_ip() {
+        assert!(is_private_ip("192.168.1.10"));
+        assert!(is_private_ip("10.0.0.1"));
+        assert!(is_private_ip("172.16.0.5"));
+        assert!(!is_private_ip("8.8.8.8"));
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(500), "500B");
+        assert_eq!(format_bytes(1500), "1.5KB");
+        assert_eq!(format_bytes(2_500_000), "2.5MB");
+    }
+}
@@ -1,4 +1,9 @@
+use rand::seq::SliceRandom;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
 
 /// Average path length of unsuccessful search in a Binary Search Tree.
 /// Used to normalize the anomaly score.
@@ -9,12 +14,23 @@ fn c(n: f64) -> f64 {
     2.0 * (n.ln() + 0.5772156649) - (2.0 * (n - 1.0) / n)
 }
 
+/// Draw a standard-normal sample via the Box-Muller transform.
+fn standard_normal(rng: &mut impl Rng) -> f64 {
+    let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.gen_range(0.0..1.0);
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
 /// A node in an isolation tree.
+#[derive(Serialize, Deserialize)]
 enum IsolationNode {
-    /// Internal node: split on `feature` at `threshold`.
+    /// Internal node: split the hyperplane `(point - p) . n <= 0` routes
+    /// left, otherwise right. With `extension_level = 0`, `n` has a single
+    /// non-zero component and this degenerates to the original
+    /// axis-parallel split.
     Branch {
-        feature: usize,
-        threshold: f64,
+        normal: Vec<f64>,
+        intercept: Vec<f64>,
         left: Box<IsolationNode>,
         right: Box<IsolationNode>,
     },
@@ -32,12 +48,19 @@ impl IsolationNode {
                 depth as f64 + c(*size as f64)
             }
             IsolationNode::Branch {
-                feature,
-                threshold,
+                normal,
+                intercept,
                 left,
                 right,
             } => {
-                if point[*feature] < *threshold {
+                let dot: f64 = point
+                    .iter()
+                    .zip(normal)
+                    .zip(intercept)
+                    .map(|((x, n), p)| (x - p) * n)
+                    .sum();
+
+                if dot <= 0.0 {
                     left.path_length(point, depth + 1)
                 } else {
                     right.path_length(point, depth + 1)
@@ -48,14 +71,24 @@ impl IsolationNode {
 }
 
 /// A single isolation tree.
+#[derive(Serialize, Deserialize)]
 pub struct IsolationTree {
     root: IsolationNode,
 }
 
 impl IsolationTree {
     /// Build an isolation tree from the given data with a maximum depth limit.
-    pub fn fit(data: &[Vec<f64>], max_depth: usize, rng: &mut impl Rng) -> Self {
-        let root = Self::build_node(data, 0, max_depth, rng);
+    ///
+    /// `extension_level` controls how many feature components the splitting
+    /// hyperplane is allowed to use beyond the first: `0` reproduces the
+    /// original axis-parallel splits, `n_features - 1` is fully extended.
+    pub fn fit(
+        data: &[Vec<f64>],
+        max_depth: usize,
+        extension_level: usize,
+        rng: &mut impl Rng,
+    ) -> Self {
+        let root = Self::build_node(data, 0, max_depth, extension_level, rng);
         IsolationTree { root }
     }
 
@@ -63,6 +96,7 @@ impl IsolationTree {
         data: &[Vec<f64>],
         depth: usize,
         max_depth: usize,
+        extension_level: usize,
         rng: &mut impl Rng,
     ) -> IsolationNode {
         // Base cases: max depth reached, or too few samples to split
@@ -71,30 +105,57 @@ impl IsolationTree {
         }
 
         let n_features = data[0].len();
-        let feature = rng.gen_range(0..n_features);
 
-        // Find min/max for the chosen feature
-        let mut min_val = f64::MAX;
-        let mut max_val = f64::MIN;
+        // Per-coordinate min/max, needed both to detect a degenerate node
+        // and to sample the intercept point.
+        let mut mins = vec![f64::MAX; n_features];
+        let mut maxs = vec![f64::MIN; n_features];
         for sample in data {
-            if sample[feature] < min_val {
-                min_val = sample[feature];
-            }
-            if sample[feature] > max_val {
-                max_val = sample[feature];
+            for (j, &value) in sample.iter().enumerate() {
+                mins[j] = mins[j].min(value);
+                maxs[j] = maxs[j].max(value);
             }
         }
 
-        // If all values are the same, can't split
-        if (max_val - min_val).abs() < f64::EPSILON {
+        // If every coordinate is constant, can't split
+        if mins
+            .iter()
+            .zip(&maxs)
+            .all(|(min_val, max_val)| (max_val - min_val).abs() < f64::EPSILON)
+        {
             return IsolationNode::Leaf { size: data.len() };
         }
 
-        // Random split point between min and max
-        let threshold = rng.gen_range(min_val..max_val);
+        // Random hyperplane normal, with `n_features - 1 - extension_level`
+        // randomly chosen components zeroed out.
+        let mut normal: Vec<f64> = (0..n_features).map(|_| standard_normal(rng)).collect();
+        let num_zeroed = n_features.saturating_sub(1 + extension_level);
+        let mut feature_order: Vec<usize> = (0..n_features).collect();
+        feature_order.shuffle(rng);
+        for &feature in feature_order.iter().take(num_zeroed) {
+            normal[feature] = 0.0;
+        }
 
-        let (left_data, right_data): (Vec<_>, Vec<_>) =
-            data.iter().cloned().partition(|sample| sample[feature] < threshold);
+        // Intercept point sampled uniformly within the node's bounding box.
+        let intercept: Vec<f64> = (0..n_features)
+            .map(|j| {
+                if maxs[j] > mins[j] {
+                    rng.gen_range(mins[j]..maxs[j])
+                } else {
+                    mins[j]
+                }
+            })
+            .collect();
+
+        let (left_data, right_data): (Vec<_>, Vec<_>) = data.iter().cloned().partition(|sample| {
+            let dot: f64 = sample
+                .iter()
+                .zip(&normal)
+                .zip(&intercept)
+                .map(|((x, n), p)| (x - p) * n)
+                .sum();
+            dot <= 0.0
+        });
 
         // Avoid empty partitions
         if left_data.is_empty() || right_data.is_empty() {
@@ -102,10 +163,22 @@ impl IsolationTree {
         }
 
         IsolationNode::Branch {
-            feature,
-            threshold,
-            left: Box::new(Self::build_node(&left_data, depth + 1, max_depth, rng)),
-            right: Box::new(Self::build_node(&right_data, depth + 1, max_depth, rng)),
+            normal,
+            intercept,
+            left: Box::new(Self::build_node(
+                &left_data,
+                depth + 1,
+                max_depth,
+                extension_level,
+                rng,
+            )),
+            right: Box::new(Self::build_node(
+                &right_data,
+                depth + 1,
+                max_depth,
+                extension_level,
+                rng,
+            )),
         }
     }
 
@@ -116,6 +189,7 @@ impl IsolationTree {
 }
 
 /// An ensemble of isolation trees for anomaly detection.
+#[derive(Serialize, Deserialize)]
 pub struct IsolationForest {
     trees: Vec<IsolationTree>,
     sample_size: usize,
@@ -126,7 +200,25 @@ impl IsolationForest {
     ///
     /// - `n_trees`: number of isolation trees (default: 100)
     /// - `sample_size`: subsample size for each tree (default: 256)
+    ///
+    /// Uses axis-parallel splits (`extension_level = 0`); see
+    /// [`IsolationForest::fit_extended`] to enable extended splits.
     pub fn fit(data: &[Vec<f64>], n_trees: usize, sample_size: usize) -> Self {
+        Self::fit_extended(data, n_trees, sample_size, 0)
+    }
+
+    /// Train an isolation forest with the given extension level.
+    ///
+    /// `extension_level = 0` reproduces the original axis-parallel splits;
+    /// `extension_level = n_features - 1` is fully extended (random
+    /// hyperplane splits), which avoids the rectangular low-anomaly-score
+    /// artifacts that axis-parallel splits produce along the feature axes.
+    pub fn fit_extended(
+        data: &[Vec<f64>],
+        n_trees: usize,
+        sample_size: usize,
+        extension_level: usize,
+    ) -> Self {
         let mut rng = rand::thread_rng();
         let max_depth = (sample_size as f64).log2().ceil() as usize;
         let actual_sample_size = sample_size.min(data.len());
@@ -140,7 +232,7 @@ impl IsolationForest {
                         data[idx].clone()
                     })
                     .collect();
-                IsolationTree::fit(&subsample, max_depth, &mut rng)
+                IsolationTree::fit(&subsample, max_depth, extension_level, &mut rng)
             })
             .collect();
 
@@ -168,6 +260,20 @@ impl IsolationForest {
         // Anomaly score: s = 2^(-E(h(x)) / c(n))
         2.0_f64.powf(-avg_path_length / cn)
     }
+
+    /// Persist the trained forest to `path` as JSON.
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let file = File::create(path)?;
+        serde_json::to_writer(BufWriter::new(file), self)?;
+        Ok(())
+    }
+
+    /// Load a forest previously written by [`IsolationForest::save`].
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = File::open(path)?;
+        let forest = serde_json::from_reader(BufReader::new(file))?;
+        Ok(forest)
+    }
 }
 
 #[cfg(test)]
@@ -222,4 +328,81 @@ mod tests {
         let score = forest.score(&[1.0, 2.0]);
         assert!(score >= 0.0 && score <= 1.0, "Score {} out of range", score);
     }
+
+    #[test]
+    fn test_extended_mode_separates_anomalies() {
+        let mut data: Vec<Vec<f64>> = Vec::new();
+        let mut rng = rand::thread_rng();
+        for _ in 0..200 {
+            data.push(vec![
+                0.5 + rng.gen_range(-0.1..0.1),
+                0.5 + rng.gen_range(-0.1..0.1),
+            ]);
+        }
+
+        // Fully extended: every split is a random hyperplane.
+        let forest = IsolationForest::fit_extended(&data, 100, 128, 1);
+
+        let normal_score = forest.score(&[0.5, 0.5]);
+        let anomaly_score = forest.score(&[10.0, 10.0]);
+
+        assert!(
+            anomaly_score > normal_score,
+            "Anomaly score ({}) should be greater than normal score ({})",
+            anomaly_score,
+            normal_score
+        );
+    }
+
+    #[test]
+    fn test_extended_mode_scores_are_rotationally_consistent() {
+        // A cluster plus one clear outlier, rotated 90 degrees. A fully
+        // extended forest shouldn't be biased toward axis-aligned outliers,
+        // so the outlier's score should land in a similar range either way.
+        let mut rng = rand::thread_rng();
+        let mut cluster: Vec<Vec<f64>> = (0..200)
+            .map(|_| vec![rng.gen_range(-0.1..0.1), rng.gen_range(-0.1..0.1)])
+            .collect();
+        let mut rotated_cluster: Vec<Vec<f64>> =
+            cluster.iter().map(|p| vec![-p[1], p[0]]).collect();
+
+        cluster.push(vec![5.0, 0.0]);
+        rotated_cluster.push(vec![0.0, 5.0]);
+
+        let forest = IsolationForest::fit_extended(&cluster, 100, 128, 1);
+        let rotated_forest = IsolationForest::fit_extended(&rotated_cluster, 100, 128, 1);
+
+        let score = forest.score(&[5.0, 0.0]);
+        let rotated_score = rotated_forest.score(&[0.0, 5.0]);
+
+        assert!(
+            (score - rotated_score).abs() < 0.15,
+            "scores should be similar under rotation: {} vs {}",
+            score,
+            rotated_score
+        );
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip_produces_identical_scores() {
+        let data = vec![
+            vec![1.0, 2.0],
+            vec![1.1, 2.1],
+            vec![0.9, 1.9],
+            vec![10.0, 10.0],
+        ];
+        let forest = IsolationForest::fit(&data, 20, 4);
+
+        let path = std::env::temp_dir().join(format!(
+            "isolation_forest_test_{}.json",
+            std::process::id()
+        ));
+        forest.save(&path).unwrap();
+        let loaded = IsolationForest::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        for point in &data {
+            assert_eq!(forest.score(point), loaded.score(point));
+        }
+    }
 }
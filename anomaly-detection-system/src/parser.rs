@@ -14,7 +14,7 @@ pub struct NetworkEvent {
     pub duration: f64,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
 pub enum Protocol {
     Tcp,
     Udp,
@@ -53,34 +53,154 @@ fn parse_protocol(s: &str) -> Protocol {
     }
 }
 
+/// Why a log line failed to parse, pinpointing the offending column
+/// (0-based index into the whitespace-split line).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// The line split into fewer whitespace-separated tokens than expected.
+    MissingField {
+        field: &'static str,
+        expected_count: usize,
+        found_count: usize,
+    },
+    BadTimestamp { column: usize, raw: String },
+    BadPort { field: &'static str, column: usize, raw: String },
+    BadBytes { column: usize, raw: String },
+    BadDuration { column: usize, raw: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingField {
+                field,
+                expected_count,
+                found_count,
+            } => write!(
+                f,
+                "missing field `{}`: expected {} fields, found {}",
+                field, expected_count, found_count
+            ),
+            ParseError::BadTimestamp { column, raw } => {
+                write!(f, "bad timestamp at column {}: {:?}", column, raw)
+            }
+            ParseError::BadPort { field, column, raw } => write!(
+                f,
+                "bad port `{}` at column {}: {:?}",
+                field, column, raw
+            ),
+            ParseError::BadBytes { column, raw } => {
+                write!(f, "bad bytes at column {}: {:?}", column, raw)
+            }
+            ParseError::BadDuration { column, raw } => {
+                write!(f, "bad duration at column {}: {:?}", column, raw)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+const EXPECTED_FIELDS: usize = 8;
+
+/// Parse a duration column into seconds, accepting a bare number (already
+/// in seconds) or a number with a unit suffix: `s`, `ms`, `us`, or `m`.
+///
+/// Examples: `"1.5s"` -> `1.5`, `"50ms"` -> `0.05`, `"2m"` -> `120.0`,
+/// `"0.05"` -> `0.05`.
+pub fn parse_duration(raw: &str) -> Option<f64> {
+    let raw = raw.trim();
+
+    // Longest suffix first so "ms"/"us" aren't mistaken for a trailing "s".
+    for (suffix, seconds_per_unit) in [("ms", 0.001), ("us", 0.000_001), ("s", 1.0), ("m", 60.0)] {
+        if let Some(number) = raw.strip_suffix(suffix) {
+            return number.parse::<f64>().ok().map(|n| n * seconds_per_unit);
+        }
+    }
+
+    raw.parse::<f64>().ok()
+}
+
+/// Parse a bytes column, accepting a bare integer or a decimal size with a
+/// `K`/`KB`, `M`/`MB`, or `G`/`GB` suffix interpreted as powers of 1000.
+///
+/// Examples: `"50K"` -> `50_000`, `"1.5MB"` -> `1_500_000`, `"1500"` -> `1500`.
+pub fn parse_bytes(raw: &str) -> Option<u64> {
+    let raw = raw.trim();
+
+    for (suffix, multiplier) in [
+        ("GB", 1_000_000_000.0),
+        ("G", 1_000_000_000.0),
+        ("MB", 1_000_000.0),
+        ("M", 1_000_000.0),
+        ("KB", 1_000.0),
+        ("K", 1_000.0),
+    ] {
+        if let Some(number) = raw.strip_suffix(suffix) {
+            return number.parse::<f64>().ok().map(|n| (n * multiplier) as u64);
+        }
+    }
+
+    raw.parse::<u64>().ok()
+}
+
 /// Parse a single line of network traffic data.
 ///
 /// Expected format (space-separated):
 /// `timestamp src_ip src_port dst_ip dst_port protocol bytes duration`
 ///
+/// The `bytes` and `duration` columns accept either a bare number or a
+/// human-readable suffix; see [`parse_bytes`] and [`parse_duration`].
+///
 /// Example:
 /// `2024-01-15T10:30:00 192.168.1.10 54321 10.0.0.1 443 TCP 1500 0.05`
-pub fn parse_line(line: &str) -> Option<NetworkEvent> {
+///
+/// Returns `Ok(None)` for blank or comment (`#`) lines, and `Err` with the
+/// exact column that failed to parse otherwise.
+pub fn parse_line(line: &str) -> Result<Option<NetworkEvent>, ParseError> {
     let line = line.trim();
     if line.is_empty() || line.starts_with('#') {
-        return None;
+        return Ok(None);
     }
 
     let parts: Vec<&str> = line.split_whitespace().collect();
-    if parts.len() < 8 {
-        return None;
+    if parts.len() < EXPECTED_FIELDS {
+        return Err(ParseError::MissingField {
+            field: "line",
+            expected_count: EXPECTED_FIELDS,
+            found_count: parts.len(),
+        });
     }
 
-    let timestamp = NaiveDateTime::parse_from_str(parts[0], "%Y-%m-%dT%H:%M:%S").ok()?;
+    let timestamp = NaiveDateTime::parse_from_str(parts[0], "%Y-%m-%dT%H:%M:%S").map_err(|_| {
+        ParseError::BadTimestamp {
+            column: 0,
+            raw: parts[0].to_string(),
+        }
+    })?;
     let src_ip = parts[1].to_string();
-    let src_port: u16 = parts[2].parse().ok()?;
+    let src_port: u16 = parts[2].parse().map_err(|_| ParseError::BadPort {
+        field: "src_port",
+        column: 2,
+        raw: parts[2].to_string(),
+    })?;
     let dst_ip = parts[3].to_string();
-    let dst_port: u16 = parts[4].parse().ok()?;
+    let dst_port: u16 = parts[4].parse().map_err(|_| ParseError::BadPort {
+        field: "dst_port",
+        column: 4,
+        raw: parts[4].to_string(),
+    })?;
     let protocol = parse_protocol(parts[5]);
-    let bytes: u64 = parts[6].parse().ok()?;
-    let duration: f64 = parts[7].parse().ok()?;
+    let bytes: u64 = parse_bytes(parts[6]).ok_or_else(|| ParseError::BadBytes {
+        column: 6,
+        raw: parts[6].to_string(),
+    })?;
+    let duration: f64 = parse_duration(parts[7]).ok_or_else(|| ParseError::BadDuration {
+        column: 7,
+        raw: parts[7].to_string(),
+    })?;
 
-    Some(NetworkEvent {
+    Ok(Some(NetworkEvent {
         timestamp,
         src_ip,
         src_port,
@@ -89,7 +209,26 @@ pub fn parse_line(line: &str) -> Option<NetworkEvent> {
         protocol,
         bytes,
         duration,
-    })
+    }))
+}
+
+/// Parse every line of a log, returning the successfully-parsed events
+/// alongside `(line_number, error)` pairs for every line that was dropped.
+///
+/// `line_number` is 1-based to match how log files are usually reported.
+pub fn parse_lines(text: &str) -> (Vec<NetworkEvent>, Vec<(usize, ParseError)>) {
+    let mut events = Vec::new();
+    let mut errors = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        match parse_line(line) {
+            Ok(Some(event)) => events.push(event),
+            Ok(None) => {}
+            Err(err) => errors.push((i + 1, err)),
+        }
+    }
+
+    (events, errors)
 }
 
 #[cfg(test)]
@@ -99,7 +238,7 @@ mod tests {
     #[test]
     fn test_parse_valid_line() {
         let line = "2024-01-15T10:30:00 192.168.1.10 54321 10.0.0.1 443 TCP 1500 0.05";
-        let event = parse_line(line).unwrap();
+        let event = parse_line(line).unwrap().unwrap();
         assert_eq!(event.src_ip, "192.168.1.10");
         assert_eq!(event.dst_port, 443);
         assert_eq!(event.protocol, Protocol::Tcp);
@@ -108,24 +247,86 @@ mod tests {
 
     #[test]
     fn test_parse_comment_line() {
-        assert!(parse_line("# this is a comment").is_none());
+        assert_eq!(parse_line("# this is a comment"), Ok(None));
     }
 
     #[test]
     fn test_parse_empty_line() {
-        assert!(parse_line("").is_none());
-        assert!(parse_line("   ").is_none());
+        assert_eq!(parse_line(""), Ok(None));
+        assert_eq!(parse_line("   "), Ok(None));
     }
 
     #[test]
     fn test_parse_malformed_line() {
-        assert!(parse_line("not enough fields").is_none());
+        assert_eq!(
+            parse_line("not enough fields"),
+            Err(ParseError::MissingField {
+                field: "line",
+                expected_count: EXPECTED_FIELDS,
+                found_count: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_bad_port_pinpoints_column() {
+        let line = "2024-01-15T10:30:00 192.168.1.10 not-a-port 10.0.0.1 443 TCP 1500 0.05";
+        assert_eq!(
+            parse_line(line),
+            Err(ParseError::BadPort {
+                field: "src_port",
+                column: 2,
+                raw: "not-a-port".to_string(),
+            })
+        );
     }
 
     #[test]
     fn test_parse_udp() {
         let line = "2024-01-15T10:30:00 10.0.0.1 12345 10.0.0.2 53 UDP 64 0.01";
-        let event = parse_line(line).unwrap();
+        let event = parse_line(line).unwrap().unwrap();
         assert_eq!(event.protocol, Protocol::Udp);
     }
+
+    #[test]
+    fn test_parse_duration_units() {
+        assert_eq!(parse_duration("1.5s"), Some(1.5));
+        assert_eq!(parse_duration("50ms"), Some(0.05));
+        assert_eq!(parse_duration("2m"), Some(120.0));
+        assert_eq!(parse_duration("500us"), Some(0.0005));
+    }
+
+    #[test]
+    fn test_parse_duration_bare_number_stays_seconds() {
+        assert_eq!(parse_duration("0.05"), Some(0.05));
+    }
+
+    #[test]
+    fn test_parse_bytes_units() {
+        assert_eq!(parse_bytes("50K"), Some(50_000));
+        assert_eq!(parse_bytes("1.5MB"), Some(1_500_000));
+        assert_eq!(parse_bytes("2GB"), Some(2_000_000_000));
+    }
+
+    #[test]
+    fn test_parse_bytes_bare_number_stays_bytes() {
+        assert_eq!(parse_bytes("1500"), Some(1500));
+    }
+
+    #[test]
+    fn test_parse_line_accepts_human_readable_columns() {
+        let line = "2024-01-15T10:30:00 192.168.1.10 54321 10.0.0.1 443 TCP 1.5MB 50ms";
+        let event = parse_line(line).unwrap().unwrap();
+        assert_eq!(event.bytes, 1_500_000);
+        assert_eq!(event.duration, 0.05);
+    }
+
+    #[test]
+    fn test_parse_lines_reports_line_numbers() {
+        let log = "2024-01-15T10:30:00 10.0.0.1 12345 10.0.0.2 53 UDP 64 0.01\nbad line\n# comment\n";
+        let (events, errors) = parse_lines(log);
+        assert_eq!(events.len(), 1);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, 2);
+    }
 }
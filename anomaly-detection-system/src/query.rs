@@ -0,0 +1,232 @@
+use crate::parser::{NetworkEvent, Protocol};
+use std::collections::HashMap;
+use std::fmt;
+
+/// What to order a query's results by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    Bytes,
+    Duration,
+    Timestamp,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    /// A resolved `offset`/`limit` fell outside `[0, total]`.
+    IndexOutOfRange { requested: i64, total: usize },
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryError::IndexOutOfRange { requested, total } => write!(
+                f,
+                "index {} is out of range for {} result(s)",
+                requested, total
+            ),
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+/// Resolve a signed index against a collection of `total` items: a
+/// non-negative index is used as-is, a negative index counts back from the
+/// end (`-1` is the last item). Returns `QueryError::IndexOutOfRange` if the
+/// resolved index falls outside `[0, total]` rather than clamping into it.
+fn get_index(i: i64, total: usize) -> Result<usize, QueryError> {
+    let resolved = if i >= 0 { i } else { total as i64 + i };
+
+    if resolved < 0 || resolved > total as i64 {
+        return Err(QueryError::IndexOutOfRange {
+            requested: i,
+            total,
+        });
+    }
+
+    Ok(resolved as usize)
+}
+
+/// A filter/sort/slice query over a borrowed `&[NetworkEvent]`, modeled on
+/// `:sort`/`:limit`/`:offset` style query options with signed indexing.
+pub struct Query<'a> {
+    events: Vec<&'a NetworkEvent>,
+}
+
+impl<'a> Query<'a> {
+    pub fn new(events: &'a [NetworkEvent]) -> Self {
+        Self {
+            events: events.iter().collect(),
+        }
+    }
+
+    /// Keep only events matching an arbitrary predicate.
+    pub fn filter<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&NetworkEvent) -> bool,
+    {
+        self.events.retain(|e| predicate(e));
+        self
+    }
+
+    pub fn protocol(self, protocol: Protocol) -> Self {
+        self.filter(move |e| e.protocol == protocol)
+    }
+
+    /// Keep events where either the source or destination port matches.
+    pub fn port(self, port: u16) -> Self {
+        self.filter(move |e| e.src_port == port || e.dst_port == port)
+    }
+
+    pub fn sort_by(mut self, key: SortKey) -> Self {
+        match key {
+            SortKey::Bytes => self.events.sort_by_key(|e| e.bytes),
+            SortKey::Duration => {
+                self.events
+                    .sort_by(|a, b| a.duration.partial_cmp(&b.duration).unwrap())
+            }
+            SortKey::Timestamp => self.events.sort_by_key(|e| e.timestamp),
+        }
+        self
+    }
+
+    /// Drop the first `offset` results (or, if negative, everything before
+    /// the `-offset`-th-from-last result).
+    pub fn offset(mut self, offset: i64) -> Result<Self, QueryError> {
+        let start = get_index(offset, self.events.len())?;
+        self.events.drain(0..start);
+        Ok(self)
+    }
+
+    /// Keep only the first `limit` remaining results (or, if negative, only
+    /// the last `-limit` of them).
+    pub fn limit(mut self, limit: i64) -> Result<Self, QueryError> {
+        let total = self.events.len();
+        let resolved = get_index(limit, total)?;
+        if limit >= 0 {
+            self.events.truncate(resolved);
+        } else {
+            self.events.drain(0..resolved);
+        }
+        Ok(self)
+    }
+
+    pub fn collect(self) -> Vec<&'a NetworkEvent> {
+        self.events
+    }
+
+    pub fn count(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.events.iter().map(|e| e.bytes).sum()
+    }
+
+    /// The `n` source IPs with the highest summed `bytes`, descending.
+    pub fn top_talkers(&self, n: usize) -> Vec<(String, u64)> {
+        let mut totals: HashMap<&str, u64> = HashMap::new();
+        for event in &self.events {
+            *totals.entry(event.src_ip.as_str()).or_insert(0) += event.bytes;
+        }
+
+        let mut ranked: Vec<(String, u64)> = totals
+            .into_iter()
+            .map(|(ip, bytes)| (ip.to_string(), bytes))
+            .collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1));
+        ranked.truncate(n);
+        ranked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+
+    fn event(src: &str, bytes: u64, duration: f64, protocol: Protocol) -> NetworkEvent {
+        NetworkEvent {
+            timestamp: NaiveDateTime::parse_from_str("2024-01-15T10:30:00", "%Y-%m-%dT%H:%M:%S")
+                .unwrap(),
+            src_ip: src.to_string(),
+            src_port: 1234,
+            dst_ip: "10.0.0.1".to_string(),
+            dst_port: 443,
+            protocol,
+            bytes,
+            duration,
+        }
+    }
+
+    #[test]
+    fn test_filter_by_protocol() {
+        let events = vec![
+            event("a", 100, 0.1, Protocol::Tcp),
+            event("b", 200, 0.1, Protocol::Udp),
+        ];
+        let results = Query::new(&events).protocol(Protocol::Udp).collect();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].src_ip, "b");
+    }
+
+    #[test]
+    fn test_sort_and_limit() {
+        let events = vec![
+            event("a", 300, 0.1, Protocol::Tcp),
+            event("b", 100, 0.1, Protocol::Tcp),
+            event("c", 200, 0.1, Protocol::Tcp),
+        ];
+        let results = Query::new(&events)
+            .sort_by(SortKey::Bytes)
+            .limit(2)
+            .unwrap()
+            .collect();
+        assert_eq!(results.iter().map(|e| e.bytes).collect::<Vec<_>>(), vec![100, 200]);
+    }
+
+    #[test]
+    fn test_negative_offset_takes_the_tail() {
+        let events = vec![
+            event("a", 1, 0.0, Protocol::Tcp),
+            event("b", 2, 0.0, Protocol::Tcp),
+            event("c", 3, 0.0, Protocol::Tcp),
+        ];
+        let results = Query::new(&events).offset(-2).unwrap().collect();
+        assert_eq!(results.iter().map(|e| e.bytes).collect::<Vec<_>>(), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_negative_limit_keeps_the_tail() {
+        let events = vec![
+            event("a", 1, 0.0, Protocol::Tcp),
+            event("b", 2, 0.0, Protocol::Tcp),
+            event("c", 3, 0.0, Protocol::Tcp),
+        ];
+        let results = Query::new(&events).limit(-1).unwrap().collect();
+        assert_eq!(results.iter().map(|e| e.bytes).collect::<Vec<_>>(), vec![3]);
+    }
+
+    #[test]
+    fn test_out_of_range_index_errors() {
+        let events = vec![event("a", 1, 0.0, Protocol::Tcp)];
+        assert_eq!(
+            Query::new(&events).offset(5).unwrap_err(),
+            QueryError::IndexOutOfRange {
+                requested: 5,
+                total: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_top_talkers() {
+        let events = vec![
+            event("a", 100, 0.0, Protocol::Tcp),
+            event("a", 50, 0.0, Protocol::Tcp),
+            event("b", 500, 0.0, Protocol::Tcp),
+        ];
+        let top = Query::new(&events).top_talkers(1);
+        assert_eq!(top, vec![("b".to_string(), 500)]);
+    }
+}
@@ -1,25 +1,120 @@
 use crate::parser::NetworkEvent;
 use colored::Colorize;
 use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::fmt;
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::Path;
 
-#[derive(Debug, Serialize)]
+/// How anomalous a report is, derived from which outlier fence (or fixed
+/// score band) the score crossed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Low => write!(f, "LOW"),
+            Severity::Medium => write!(f, "MEDIUM"),
+            Severity::High => write!(f, "HIGH"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct AnomalyReport {
     pub event: NetworkEvent,
     pub score: f64,
     pub event_number: usize,
+    pub severity: Severity,
+}
+
+/// Orders `AnomalyReport`s by score in reverse (breaking ties by
+/// `event_number`, also in reverse), so a `BinaryHeap` of these surfaces the
+/// *lowest*-scoring, earliest entry first and can be used as a min-heap.
+/// The tie-break matters: without it, two distinct events with equal scores
+/// compare `Equal` and `offer` can never tell which one to evict.
+struct ScoredReport(AnomalyReport);
+
+impl PartialEq for ScoredReport {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ScoredReport {}
+
+impl PartialOrd for ScoredReport {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScoredReport {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .0
+            .score
+            .partial_cmp(&self.0.score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.0.event_number.cmp(&self.0.event_number))
+    }
+}
+
+/// Tracks the `capacity` highest-scoring anomalies seen in a session,
+/// without retaining every report, for an end-of-session "worst offenders"
+/// summary.
+pub struct TopAnomalies {
+    capacity: usize,
+    heap: BinaryHeap<ScoredReport>,
+}
+
+impl TopAnomalies {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            heap: BinaryHeap::with_capacity(capacity),
+        }
+    }
+
+    /// Offer a report to the tracker. If still under capacity it is kept
+    /// outright; otherwise it replaces the current lowest-scoring entry if
+    /// it scores higher.
+    pub fn offer(&mut self, report: AnomalyReport) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.heap.len() < self.capacity {
+            self.heap.push(ScoredReport(report));
+        } else if let Some(lowest) = self.heap.peek() {
+            if report.score > lowest.0.score {
+                self.heap.pop();
+                self.heap.push(ScoredReport(report));
+            }
+        }
+    }
+
+    /// The tracked reports, ordered from highest to lowest score.
+    pub fn top(&self) -> Vec<&AnomalyReport> {
+        let mut reports: Vec<&AnomalyReport> = self.heap.iter().map(|scored| &scored.0).collect();
+        reports.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+        reports
+    }
 }
 
 /// Print an anomaly to the terminal with colored output.
 pub fn print_anomaly(report: &AnomalyReport) {
-    let severity = if report.score >= 0.8 {
-        "HIGH".red().bold()
-    } else if report.score >= 0.7 {
-        "MEDIUM".yellow().bold()
-    } else {
-        "LOW".yellow()
+    let severity = match report.severity {
+        Severity::High => "HIGH".red().bold(),
+        Severity::Medium => "MEDIUM".yellow().bold(),
+        Severity::Low => "LOW".yellow(),
     };
 
     let event = &report.event;
@@ -68,6 +163,26 @@ pub fn print_summary(total_events: usize, total_anomalies: usize) {
     eprintln!("Anomaly rate:           {:.2}%", rate);
 }
 
+/// Print the highest-scoring anomalies tracked over the session.
+pub fn print_top_anomalies(tracker: &TopAnomalies) {
+    eprintln!();
+    eprintln!("{}", "=== Top Anomalies ===".bold());
+    for (rank, report) in tracker.top().into_iter().enumerate() {
+        let event = &report.event;
+        eprintln!(
+            "{:>2}. #{} | {}:{} -> {}:{} | {} | score: {:.4}",
+            rank + 1,
+            report.event_number,
+            event.src_ip,
+            event.src_port,
+            event.dst_ip,
+            event.dst_port,
+            event.protocol,
+            report.score,
+        );
+    }
+}
+
 /// Print a status update during processing.
 pub fn print_status(total_events: usize, total_anomalies: usize, trained: bool) {
     let status = if trained {
@@ -80,3 +195,64 @@ pub fn print_status(total_events: usize, total_anomalies: usize, trained: bool)
         status, total_events, total_anomalies
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Protocol;
+    use chrono::NaiveDate;
+
+    fn make_report(event_number: usize, score: f64) -> AnomalyReport {
+        AnomalyReport {
+            event: NetworkEvent {
+                timestamp: NaiveDate::from_ymd_opt(2024, 1, 1)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+                src_ip: "10.0.0.1".to_string(),
+                src_port: 1234,
+                dst_ip: "8.8.8.8".to_string(),
+                dst_port: 443,
+                protocol: Protocol::Tcp,
+                bytes: 100,
+                duration: 0.1,
+            },
+            score,
+            event_number,
+            severity: Severity::High,
+        }
+    }
+
+    #[test]
+    fn test_top_anomalies_keeps_exactly_the_k_largest() {
+        let mut tracker = TopAnomalies::new(3);
+        let scores = [0.1, 0.9, 0.4, 0.95, 0.2, 0.99, 0.3, 0.85, 0.0, 0.5];
+
+        for (i, &score) in scores.iter().enumerate() {
+            tracker.offer(make_report(i, score));
+        }
+
+        let top: Vec<f64> = tracker.top().iter().map(|report| report.score).collect();
+        assert_eq!(top, vec![0.99, 0.95, 0.9]);
+    }
+
+    #[test]
+    fn test_top_anomalies_keeps_distinct_events_with_equal_scores() {
+        // Distinct events with identical scores are each kept rather than
+        // collapsing into one entry; a third equal score does not displace
+        // either, since `offer` only evicts for a strictly higher score.
+        let mut tracker = TopAnomalies::new(2);
+        tracker.offer(make_report(1, 0.5));
+        tracker.offer(make_report(2, 0.5));
+        tracker.offer(make_report(3, 0.5));
+
+        assert_eq!(tracker.top().len(), 2);
+    }
+
+    #[test]
+    fn test_top_anomalies_respects_capacity_zero() {
+        let mut tracker = TopAnomalies::new(0);
+        tracker.offer(make_report(1, 1.0));
+        assert!(tracker.top().is_empty());
+    }
+}
@@ -0,0 +1,201 @@
+use crate::parser::{parse_line, NetworkEvent, ParseError};
+use std::io::BufRead;
+
+/// A blocking, pull-based source of `NetworkEvent`s, e.g. a file or stdin.
+pub trait SyncSource {
+    /// Return the next successfully-parsed event, `Ok(None)` once the
+    /// source is exhausted, or `Err` for a line that failed to parse.
+    /// Blank/comment lines are skipped transparently.
+    fn next_event(&mut self) -> Result<Option<NetworkEvent>, ParseError>;
+}
+
+/// An async counterpart to [`SyncSource`] for tailing sockets or pipes.
+#[cfg(feature = "tokio-source")]
+#[async_trait::async_trait]
+pub trait AsyncSource {
+    async fn next_event(&mut self) -> Result<Option<NetworkEvent>, ParseError>;
+}
+
+/// Adapts any buffered reader (a file, stdin, a `&[u8]`) into a `SyncSource`
+/// by parsing one line at a time with [`parse_line`].
+pub struct BufReaderSource<R: BufRead> {
+    reader: R,
+}
+
+impl<R: BufRead> BufReaderSource<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+}
+
+impl<R: BufRead> SyncSource for BufReaderSource<R> {
+    fn next_event(&mut self) -> Result<Option<NetworkEvent>, ParseError> {
+        loop {
+            let mut line = String::new();
+            let bytes_read = self
+                .reader
+                .read_line(&mut line)
+                .unwrap_or(0);
+
+            if bytes_read == 0 {
+                return Ok(None);
+            }
+
+            match parse_line(&line)? {
+                Some(event) => return Ok(Some(event)),
+                None => continue,
+            }
+        }
+    }
+}
+
+/// Tails a growing file, polling for newly appended lines. Reuses
+/// [`parse_line`] and skips blank/comment lines just like
+/// [`BufReaderSource`].
+#[cfg(feature = "tokio-source")]
+pub struct TailSource {
+    file: tokio::fs::File,
+    buffer: Vec<u8>,
+    poll_interval: std::time::Duration,
+}
+
+#[cfg(feature = "tokio-source")]
+impl TailSource {
+    pub async fn open(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        Ok(Self {
+            file: tokio::fs::File::open(path).await?,
+            buffer: Vec::new(),
+            poll_interval: std::time::Duration::from_millis(250),
+        })
+    }
+
+    pub fn with_poll_interval(mut self, interval: std::time::Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+}
+
+#[cfg(feature = "tokio-source")]
+#[async_trait::async_trait]
+impl AsyncSource for TailSource {
+    async fn next_event(&mut self) -> Result<Option<NetworkEvent>, ParseError> {
+        use tokio::io::AsyncReadExt;
+
+        loop {
+            if let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+                let line = String::from_utf8_lossy(&line);
+                if let Some(event) = parse_line(&line)? {
+                    return Ok(Some(event));
+                }
+                continue;
+            }
+
+            let mut chunk = [0u8; 4096];
+            match self.file.read(&mut chunk).await {
+                Ok(0) => tokio::time::sleep(self.poll_interval).await,
+                Ok(n) => self.buffer.extend_from_slice(&chunk[..n]),
+                Err(_) => tokio::time::sleep(self.poll_interval).await,
+            }
+        }
+    }
+}
+
+/// Counts how many lines a source dropped to `ParseError`s, keyed by a
+/// caller-supplied source label.
+#[derive(Debug, Default, Clone)]
+pub struct ErrorCounts {
+    counts: std::collections::HashMap<String, usize>,
+}
+
+impl ErrorCounts {
+    pub fn record(&mut self, source_label: &str) {
+        *self.counts.entry(source_label.to_string()).or_insert(0) += 1;
+    }
+
+    pub fn for_source(&self, source_label: &str) -> usize {
+        self.counts.get(source_label).copied().unwrap_or(0)
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+}
+
+/// Drives a [`SyncSource`] to completion, forwarding every successfully
+/// parsed event into a callback and tallying parse errors per source.
+pub struct Pipeline {
+    error_counts: ErrorCounts,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Self {
+            error_counts: ErrorCounts::default(),
+        }
+    }
+
+    /// Run `source` to exhaustion, calling `on_event` for each parsed
+    /// `NetworkEvent`. Parse errors are tallied under `source_label` rather
+    /// than stopping the pipeline.
+    pub fn run<S: SyncSource>(
+        &mut self,
+        source_label: &str,
+        mut source: S,
+        mut on_event: impl FnMut(NetworkEvent),
+    ) {
+        loop {
+            match source.next_event() {
+                Ok(Some(event)) => on_event(event),
+                Ok(None) => break,
+                Err(_) => self.error_counts.record(source_label),
+            }
+        }
+    }
+
+    pub fn error_counts(&self) -> &ErrorCounts {
+        &self.error_counts
+    }
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_buf_reader_source_skips_blank_and_comment_lines() {
+        let log = "# comment\n\n2024-01-15T10:30:00 10.0.0.1 1 10.0.0.2 2 TCP 100 0.01\n";
+        let mut source = BufReaderSource::new(Cursor::new(log));
+        let event = source.next_event().unwrap().unwrap();
+        assert_eq!(event.src_ip, "10.0.0.1");
+        assert!(source.next_event().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_buf_reader_source_surfaces_parse_errors() {
+        let log = "not a valid line\n";
+        let mut source = BufReaderSource::new(Cursor::new(log));
+        assert!(source.next_event().is_err());
+    }
+
+    #[test]
+    fn test_pipeline_forwards_events_and_tallies_errors() {
+        let log = "bad line\n2024-01-15T10:30:00 10.0.0.1 1 10.0.0.2 2 TCP 100 0.01\n";
+        let source = BufReaderSource::new(Cursor::new(log));
+        let mut pipeline = Pipeline::new();
+        let mut received = Vec::new();
+
+        pipeline.run("test.log", source, |event| received.push(event));
+
+        assert_eq!(received.len(), 1);
+        assert_eq!(pipeline.error_counts().for_source("test.log"), 1);
+        assert_eq!(pipeline.error_counts().total(), 1);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::config::ChatbotConfig;
+use crate::conversation::{ConversationContext, ConversationManager};
+use crate::entity::Entity;
+use crate::intent::Intent;
+use std::path::Path;
+
+/// A small rule-based chatbot: matches an incoming message against the
+/// configured intent triggers, extracts any configured entities, and fills
+/// the winning intent's response template.
+pub struct Chatbot {
+    config: ChatbotConfig,
+    conversations: ConversationManager,
+}
+
+impl Chatbot {
+    /// Build a chatbot with the built-in default intents/entities.
+    pub fn new() -> Self {
+        Self::from_config(ChatbotConfig::default_config())
+    }
+
+    pub fn from_config(config: ChatbotConfig) -> Self {
+        Chatbot {
+            config,
+            conversations: ConversationManager::new(),
+        }
+    }
+
+    pub fn from_config_path<P: AsRef<Path>>(
+        path: P,
+    ) -> Result<Self, crate::config::ConfigError> {
+        Ok(Self::from_config(ChatbotConfig::from_path(path)?))
+    }
+
+    /// Process one user message within `session_id`'s conversation and
+    /// return the bot's reply.
+    pub fn process_message(&mut self, session_id: &str, input: &str) -> String {
+        self.conversations
+            .get_or_create_session(session_id.to_string());
+
+        let intent = self.recognize_intent(input);
+        let entities = self.extract_entities(input);
+        let response = self.render_response(&intent, &entities);
+
+        self.conversations.record_turn(
+            session_id,
+            input.to_string(),
+            response.clone(),
+            intent,
+            entities,
+        );
+
+        response
+    }
+
+    fn recognize_intent(&self, input: &str) -> Intent {
+        let lower = input.to_lowercase();
+        for intent_config in &self.config.intents {
+            if intent_config
+                .triggers
+                .iter()
+                .any(|trigger| lower.contains(trigger.as_str()))
+            {
+                return Intent::from_name(&intent_config.name);
+            }
+        }
+        Intent::Unknown
+    }
+
+    fn extract_entities(&self, input: &str) -> Vec<Entity> {
+        let lower = input.to_lowercase();
+        let mut entities = Vec::new();
+        for entity_config in &self.config.entities {
+            for keyword in &entity_config.keywords {
+                if lower.contains(keyword.as_str()) {
+                    entities.push(Entity::new(entity_config.name.clone(), keyword.clone()));
+                    break;
+                }
+            }
+        }
+        entities
+    }
+
+    fn render_response(&self, intent: &Intent, entities: &[Entity]) -> String {
+        let template = self
+            .config
+            .intents
+            .iter()
+            .find(|i| i.name == intent.name())
+            .and_then(|i| i.responses.first())
+            .cloned();
+
+        let Some(mut response) = template else {
+            return "I'm not sure I understand. Try asking for 'help'.".to_string();
+        };
+
+        for entity in entities {
+            let placeholder = format!("{{{}}}", entity.name);
+            response = response.replace(&placeholder, &entity.value);
+        }
+
+        response
+    }
+
+    pub fn get_conversation_context(&self, session_id: &str) -> Option<&ConversationContext> {
+        self.conversations.get_session(session_id)
+    }
+
+    pub fn end_conversation(&mut self, session_id: &str) {
+        self.conversations.end_session(session_id);
+    }
+}
+
+impl Default for Chatbot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_greeting_intent_is_recognized() {
+        let mut bot = Chatbot::new();
+        let response = bot.process_message("s1", "Hello!");
+        assert_eq!(response, "Hi there! How can I help you today?");
+    }
+
+    #[test]
+    fn test_entity_fills_response_template() {
+        let mut bot = Chatbot::new();
+        let response = bot.process_message("s1", "What's the weather in London?");
+        assert_eq!(response, "Here's the forecast for london: sunny and mild.");
+    }
+
+    #[test]
+    fn test_custom_config_changes_behavior_without_rebuild() {
+        let toml_str = r#"
+            [[intent]]
+            name = "farewell"
+            triggers = ["bye"]
+            responses = ["Catch you later!"]
+        "#;
+        let config = ChatbotConfig::from_str(toml_str).unwrap();
+        let mut bot = Chatbot::from_config(config);
+        assert_eq!(bot.process_message("s1", "bye"), "Catch you later!");
+    }
+
+    #[test]
+    fn test_unknown_intent_falls_back() {
+        let mut bot = Chatbot::new();
+        let response = bot.process_message("s1", "asdfqwerty");
+        assert_eq!(response, "I'm not sure I understand. Try asking for 'help'.");
+    }
+}
@@ -0,0 +1,185 @@
+use serde::Deserialize;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// A single recognizable intent: the phrases that trigger it, a couple of
+/// example utterances (documentation only), and the response template(s);
+/// the bot replies with the first one. Templates may reference
+/// `{entity_name}` placeholders filled in from extracted entities.
+#[derive(Debug, Clone, Deserialize)]
+pub struct IntentConfig {
+    pub name: String,
+    #[serde(default)]
+    pub triggers: Vec<String>,
+    #[serde(default)]
+    pub examples: Vec<String>,
+    pub responses: Vec<String>,
+}
+
+/// A named entity pattern: a literal keyword list and an optional regex.
+/// `Chatbot` extracts entities by keyword only; `regex` is parsed from the
+/// config but not used for matching.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EntityConfig {
+    pub name: String,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub regex: Option<String>,
+}
+
+/// The full set of `[[intent]]` and `[[entity]]` tables a `Chatbot` is
+/// built from. Loading a new `ChatbotConfig` from a TOML file changes bot
+/// behavior with no rebuild.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatbotConfig {
+    #[serde(rename = "intent", default)]
+    pub intents: Vec<IntentConfig>,
+    #[serde(rename = "entity", default)]
+    pub entities: Vec<EntityConfig>,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "failed to read chatbot config: {}", e),
+            ConfigError::Toml(e) => write!(f, "failed to parse chatbot config: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<std::io::Error> for ConfigError {
+    fn from(e: std::io::Error) -> Self {
+        ConfigError::Io(e)
+    }
+}
+
+impl From<toml::de::Error> for ConfigError {
+    fn from(e: toml::de::Error) -> Self {
+        ConfigError::Toml(e)
+    }
+}
+
+impl ChatbotConfig {
+    pub fn from_str(toml_str: &str) -> Result<Self, ConfigError> {
+        Ok(toml::from_str(toml_str)?)
+    }
+
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        let contents = fs::read_to_string(path)?;
+        Self::from_str(&contents)
+    }
+
+    /// The built-in intents/entities the chatbot ships with, used when no
+    /// config file is supplied.
+    pub fn default_config() -> Self {
+        ChatbotConfig {
+            intents: vec![
+                IntentConfig {
+                    name: "greeting".to_string(),
+                    triggers: vec!["hello".to_string(), "hi".to_string(), "hey".to_string()],
+                    examples: vec!["Hello!".to_string()],
+                    responses: vec!["Hi there! How can I help you today?".to_string()],
+                },
+                IntentConfig {
+                    name: "booking".to_string(),
+                    triggers: vec!["book".to_string(), "appointment".to_string(), "schedule".to_string()],
+                    examples: vec!["Book an appointment for tomorrow at 3pm".to_string()],
+                    responses: vec![
+                        "Sure, I've noted your appointment request for {date}.".to_string(),
+                    ],
+                },
+                IntentConfig {
+                    name: "weather".to_string(),
+                    triggers: vec!["weather".to_string(), "forecast".to_string(), "temperature".to_string()],
+                    examples: vec!["What's the weather in New York?".to_string()],
+                    responses: vec!["Here's the forecast for {city}: sunny and mild.".to_string()],
+                },
+                IntentConfig {
+                    name: "order".to_string(),
+                    triggers: vec!["order".to_string(), "food".to_string(), "buy".to_string()],
+                    examples: vec!["I want to order food".to_string()],
+                    responses: vec!["Got it, starting an order for {item}.".to_string()],
+                },
+                IntentConfig {
+                    name: "help".to_string(),
+                    triggers: vec!["help".to_string()],
+                    examples: vec!["Help".to_string()],
+                    responses: vec![
+                        "I can help you book appointments, check the weather, or place an order."
+                            .to_string(),
+                    ],
+                },
+            ],
+            entities: vec![
+                EntityConfig {
+                    name: "date".to_string(),
+                    keywords: vec!["today".to_string(), "tomorrow".to_string()],
+                    regex: None,
+                },
+                EntityConfig {
+                    name: "city".to_string(),
+                    keywords: vec!["new york".to_string(), "london".to_string(), "paris".to_string()],
+                    regex: None,
+                },
+                EntityConfig {
+                    name: "item".to_string(),
+                    keywords: vec!["pizza".to_string(), "sushi".to_string(), "burger".to_string()],
+                    regex: None,
+                },
+            ],
+        }
+    }
+}
+
+impl Default for ChatbotConfig {
+    fn default() -> Self {
+        Self::default_config()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_minimal_config() {
+        let toml_str = r#"
+            [[intent]]
+            name = "greeting"
+            triggers = ["hi"]
+            responses = ["Hello!"]
+
+            [[entity]]
+            name = "city"
+            keywords = ["london"]
+        "#;
+
+        let config = ChatbotConfig::from_str(toml_str).unwrap();
+        assert_eq!(config.intents.len(), 1);
+        assert_eq!(config.intents[0].name, "greeting");
+        assert_eq!(config.entities[0].name, "city");
+    }
+
+    #[test]
+    fn test_default_config_has_builtin_intents() {
+        let config = ChatbotConfig::default_config();
+        let names: Vec<&str> = config.intents.iter().map(|i| i.name.as_str()).collect();
+        assert!(names.contains(&"greeting"));
+        assert!(names.contains(&"booking"));
+    }
+
+    #[test]
+    fn test_invalid_toml_is_an_error() {
+        assert!(ChatbotConfig::from_str("not valid toml [[[").is_err());
+    }
+}
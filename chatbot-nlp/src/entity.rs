@@ -0,0 +1,16 @@
+/// A piece of information extracted from a user's message, e.g. a date,
+/// city, or dish name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entity {
+    pub name: String,
+    pub value: String,
+}
+
+impl Entity {
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Entity {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
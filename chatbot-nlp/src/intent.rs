@@ -0,0 +1,52 @@
+use std::fmt;
+
+/// The recognized purpose of a user message.
+///
+/// `Custom` carries the name of an intent loaded from a
+/// [`ChatbotConfig`](crate::config::ChatbotConfig), so new domains can be
+/// added purely through configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Intent {
+    Greeting,
+    Booking,
+    Weather,
+    Order,
+    Help,
+    Custom(String),
+    Unknown,
+}
+
+impl Intent {
+    /// The name used to look this intent up in a `ChatbotConfig`.
+    pub fn name(&self) -> &str {
+        match self {
+            Intent::Greeting => "greeting",
+            Intent::Booking => "booking",
+            Intent::Weather => "weather",
+            Intent::Order => "order",
+            Intent::Help => "help",
+            Intent::Custom(name) => name,
+            Intent::Unknown => "unknown",
+        }
+    }
+
+    /// Map a config intent name back to a well-known variant, falling back
+    /// to `Custom` for anything the chatbot doesn't have built-in handling
+    /// for.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "greeting" => Intent::Greeting,
+            "booking" => Intent::Booking,
+            "weather" => Intent::Weather,
+            "order" => Intent::Order,
+            "help" => Intent::Help,
+            other => Intent::Custom(other.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Intent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -1,16 +1,31 @@
 use crate::models::Dataset;
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 
 pub struct CollaborativeFilter<'a> {
     dataset: &'a Dataset,
+    /// Pairwise item similarities, keyed by `(min(movie1, movie2),
+    /// max(movie1, movie2))`. Empty until [`Self::build_item_similarity_matrix`]
+    /// runs, which `recommend_item_based` does lazily on first use.
+    item_similarity_cache: RefCell<HashMap<(u32, u32), f64>>,
+    /// Significance-weighting threshold γ for Pearson correlation: a
+    /// coefficient backed by `m` common ratings is shrunk by `min(m, γ)/γ`,
+    /// so neighbors with little overlap don't dominate `find_similar_users`.
+    pub significance_threshold: f64,
 }
 
 impl<'a> CollaborativeFilter<'a> {
     pub fn new(dataset: &'a Dataset) -> Self {
-        CollaborativeFilter { dataset }
+        CollaborativeFilter {
+            dataset,
+            item_similarity_cache: RefCell::new(HashMap::new()),
+            significance_threshold: 50.0,
+        }
     }
 
-    /// Calculate Pearson correlation coefficient between two users
+    /// Calculate Pearson correlation coefficient between two users, shrunk
+    /// toward zero by significance weighting when the overlap is small
+    /// (see `significance_threshold`).
     fn pearson_correlation(&self, user1_id: u32, user2_id: u32) -> f64 {
         let user1_ratings = self.get_user_rating_map(user1_id);
         let user2_ratings = self.get_user_rating_map(user2_id);
@@ -47,11 +62,9 @@ impl<'a> CollaborativeFilter<'a> {
         let num = p_sum - (sum1 * sum2 / n);
         let den = ((sum1_sq - sum1 * sum1 / n) * (sum2_sq - sum2 * sum2 / n)).sqrt();
 
-        if den == 0.0 {
-            0.0
-        } else {
-            num / den
-        }
+        let correlation = if den == 0.0 { 0.0 } else { num / den };
+        let shrinkage = n.min(self.significance_threshold) / self.significance_threshold;
+        correlation * shrinkage
     }
 
     /// Get user ratings as a map
@@ -79,6 +92,27 @@ impl<'a> CollaborativeFilter<'a> {
         similarities
     }
 
+    /// Predict `user_id`'s rating for `movie_id` as the similarity-weighted
+    /// average rating given to it by `user_id`'s nearest neighbors, falling
+    /// back to the dataset's mean rating when no neighbor has rated it.
+    pub fn predict(&self, user_id: u32, movie_id: u32) -> f64 {
+        let mut weighted_sum = 0.0;
+        let mut similarity_sum = 0.0;
+
+        for (other_id, similarity) in self.find_similar_users(user_id, 10) {
+            if let Some(&rating) = self.get_user_rating_map(other_id).get(&movie_id) {
+                weighted_sum += rating * similarity;
+                similarity_sum += similarity;
+            }
+        }
+
+        if similarity_sum == 0.0 {
+            self.dataset.mean_rating()
+        } else {
+            weighted_sum / similarity_sum
+        }
+    }
+
     /// Recommend movies using user-based collaborative filtering
     pub fn recommend(&self, user_id: u32, n: usize) -> Vec<(u32, f64)> {
         let similar_users = self.find_similar_users(user_id, 10);
@@ -119,8 +153,20 @@ impl<'a> CollaborativeFilter<'a> {
         recommendations
     }
 
-    /// Calculate item-item similarity using cosine similarity
-    fn cosine_similarity(&self, movie1_id: u32, movie2_id: u32) -> f64 {
+    /// Mean rating a user has given, used to remove per-user rating-scale
+    /// bias before computing adjusted cosine similarity.
+    fn user_mean_rating(&self, user_id: u32) -> f64 {
+        let ratings = self.get_user_rating_map(user_id);
+        if ratings.is_empty() {
+            return 0.0;
+        }
+        ratings.values().sum::<f64>() / ratings.len() as f64
+    }
+
+    /// Calculate item-item similarity using adjusted cosine similarity:
+    /// raw cosine over each common rater's ratings after subtracting that
+    /// rater's mean rating, removing per-user rating-scale bias.
+    fn adjusted_cosine_similarity(&self, movie1_id: u32, movie2_id: u32) -> f64 {
         let movie1_ratings = self.get_movie_rating_map(movie1_id);
         let movie2_ratings = self.get_movie_rating_map(movie2_id);
 
@@ -139,11 +185,12 @@ impl<'a> CollaborativeFilter<'a> {
         let mut norm2 = 0.0;
 
         for user_id in common_users {
-            let rating1 = movie1_ratings[&user_id];
-            let rating2 = movie2_ratings[&user_id];
-            dot_product += rating1 * rating2;
-            norm1 += rating1 * rating1;
-            norm2 += rating2 * rating2;
+            let mean = self.user_mean_rating(user_id);
+            let adjusted1 = movie1_ratings[&user_id] - mean;
+            let adjusted2 = movie2_ratings[&user_id] - mean;
+            dot_product += adjusted1 * adjusted2;
+            norm1 += adjusted1 * adjusted1;
+            norm2 += adjusted2 * adjusted2;
         }
 
         if norm1 == 0.0 || norm2 == 0.0 {
@@ -162,10 +209,37 @@ impl<'a> CollaborativeFilter<'a> {
             .collect()
     }
 
+    /// Compute every pairwise item similarity once using adjusted cosine
+    /// and cache the results, so repeated `recommend_item_based` calls
+    /// don't redo `O(movies² · users)` work.
+    pub fn build_item_similarity_matrix(&self) {
+        let movie_ids: Vec<u32> = self.dataset.movies.keys().copied().collect();
+        let mut cache = HashMap::new();
+
+        for (i, &movie1) in movie_ids.iter().enumerate() {
+            for &movie2 in &movie_ids[i + 1..] {
+                let similarity = self.adjusted_cosine_similarity(movie1, movie2);
+                cache.insert((movie1.min(movie2), movie1.max(movie2)), similarity);
+            }
+        }
+
+        *self.item_similarity_cache.borrow_mut() = cache;
+    }
+
+    /// Cached similarity between two items, building the cache on first use.
+    fn cached_item_similarity(&self, movie1_id: u32, movie2_id: u32) -> f64 {
+        if self.item_similarity_cache.borrow().is_empty() {
+            self.build_item_similarity_matrix();
+        }
+
+        let key = (movie1_id.min(movie2_id), movie1_id.max(movie2_id));
+        *self.item_similarity_cache.borrow().get(&key).unwrap_or(&0.0)
+    }
+
     /// Recommend movies using item-based collaborative filtering
     pub fn recommend_item_based(&self, user_id: u32, n: usize) -> Vec<(u32, f64)> {
         let user_ratings = self.get_user_rating_map(user_id);
-        
+
         if user_ratings.is_empty() {
             return Vec::new();
         }
@@ -175,7 +249,7 @@ impl<'a> CollaborativeFilter<'a> {
         for (&rated_movie_id, &user_rating) in &user_ratings {
             for &candidate_movie_id in self.dataset.movies.keys() {
                 if !user_ratings.contains_key(&candidate_movie_id) {
-                    let similarity = self.cosine_similarity(rated_movie_id, candidate_movie_id);
+                    let similarity = self.cached_item_similarity(rated_movie_id, candidate_movie_id);
                     if similarity > 0.0 {
                         let entry = movie_scores.entry(candidate_movie_id).or_insert((0.0, 0.0));
                         entry.0 += user_rating * similarity;
@@ -196,4 +270,49 @@ impl<'a> CollaborativeFilter<'a> {
         recommendations.truncate(n);
         recommendations
     }
+
+    /// Item-item similarity treating ratings as binary interaction
+    /// history: `|U_i ∩ U_j| / |U_i ∪ U_j|` over the sets of users who
+    /// interacted with each movie. Suited to presence/absence signals
+    /// (purchases, views) rather than scalar rating scores.
+    fn jaccard_similarity(&self, movie1_id: u32, movie2_id: u32) -> f64 {
+        let users1: HashSet<u32> = self.get_movie_rating_map(movie1_id).into_keys().collect();
+        let users2: HashSet<u32> = self.get_movie_rating_map(movie2_id).into_keys().collect();
+
+        let union_size = users1.union(&users2).count();
+        if union_size == 0 {
+            return 0.0;
+        }
+
+        users1.intersection(&users2).count() as f64 / union_size as f64
+    }
+
+    /// Recommend movies using item-based CF with Jaccard similarity: each
+    /// candidate unrated movie is scored by the sum of its Jaccard
+    /// similarities to every movie the user has interacted with.
+    pub fn recommend_item_based_jaccard(&self, user_id: u32, n: usize) -> Vec<(u32, f64)> {
+        let user_ratings = self.get_user_rating_map(user_id);
+
+        if user_ratings.is_empty() {
+            return Vec::new();
+        }
+
+        let mut movie_scores: HashMap<u32, f64> = HashMap::new();
+
+        for &rated_movie_id in user_ratings.keys() {
+            for &candidate_movie_id in self.dataset.movies.keys() {
+                if !user_ratings.contains_key(&candidate_movie_id) {
+                    let similarity = self.jaccard_similarity(rated_movie_id, candidate_movie_id);
+                    if similarity > 0.0 {
+                        *movie_scores.entry(candidate_movie_id).or_insert(0.0) += similarity;
+                    }
+                }
+            }
+        }
+
+        let mut recommendations: Vec<(u32, f64)> = movie_scores.into_iter().collect();
+        recommendations.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        recommendations.truncate(n);
+        recommendations
+    }
 }
@@ -0,0 +1,110 @@
+use crate::models::Dataset;
+use std::collections::{HashMap, HashSet};
+
+pub struct ContentBasedFilter<'a> {
+    dataset: &'a Dataset,
+}
+
+impl<'a> ContentBasedFilter<'a> {
+    pub fn new(dataset: &'a Dataset) -> Self {
+        ContentBasedFilter { dataset }
+    }
+
+    /// Genre-overlap (Jaccard) similarity between two movies.
+    fn genre_similarity(&self, movie1_id: u32, movie2_id: u32) -> f64 {
+        let (Some(movie1), Some(movie2)) = (
+            self.dataset.movies.get(&movie1_id),
+            self.dataset.movies.get(&movie2_id),
+        ) else {
+            return 0.0;
+        };
+
+        let genres1: HashSet<&String> = movie1.genres.iter().collect();
+        let genres2: HashSet<&String> = movie2.genres.iter().collect();
+
+        let union_size = genres1.union(&genres2).count();
+        if union_size == 0 {
+            return 0.0;
+        }
+
+        genres1.intersection(&genres2).count() as f64 / union_size as f64
+    }
+
+    /// Predict `user_id`'s rating for `movie_id` as the similarity-weighted
+    /// average rating given to genre-similar movies the user has already
+    /// rated, falling back to the dataset's mean rating otherwise.
+    pub fn predict(&self, user_id: u32, movie_id: u32) -> f64 {
+        let mut weighted_sum = 0.0;
+        let mut similarity_sum = 0.0;
+
+        for rating in self.dataset.get_user_ratings(user_id) {
+            let similarity = self.genre_similarity(rating.movie_id, movie_id);
+            if similarity > 0.0 {
+                weighted_sum += rating.rating * similarity;
+                similarity_sum += similarity;
+            }
+        }
+
+        if similarity_sum == 0.0 {
+            self.dataset.mean_rating()
+        } else {
+            weighted_sum / similarity_sum
+        }
+    }
+
+    /// Recommend movies whose genres overlap with the user's rated movies,
+    /// scoring each candidate by the similarity-weighted average of the
+    /// ratings the user gave to the movies it's similar to.
+    pub fn recommend(&self, user_id: u32, n: usize) -> Vec<(u32, f64)> {
+        let user_ratings: HashMap<u32, f64> = self
+            .dataset
+            .get_user_ratings(user_id)
+            .iter()
+            .map(|r| (r.movie_id, r.rating))
+            .collect();
+
+        if user_ratings.is_empty() {
+            return Vec::new();
+        }
+
+        let mut movie_scores: HashMap<u32, (f64, f64)> = HashMap::new(); // (weighted_sum, similarity_sum)
+
+        for (&rated_movie_id, &rating) in &user_ratings {
+            for &candidate_movie_id in self.dataset.movies.keys() {
+                if !user_ratings.contains_key(&candidate_movie_id) {
+                    let similarity = self.genre_similarity(rated_movie_id, candidate_movie_id);
+                    if similarity > 0.0 {
+                        let entry = movie_scores.entry(candidate_movie_id).or_insert((0.0, 0.0));
+                        entry.0 += rating * similarity;
+                        entry.1 += similarity;
+                    }
+                }
+            }
+        }
+
+        let mut recommendations: Vec<(u32, f64)> = movie_scores
+            .iter()
+            .map(|(&movie_id, &(weighted_sum, sim_sum))| (movie_id, weighted_sum / sim_sum))
+            .collect();
+
+        recommendations.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        recommendations.truncate(n);
+        recommendations
+    }
+
+    /// Find the `n` movies most similar to `movie_id` by genre overlap.
+    pub fn find_similar_movies(&self, movie_id: u32, n: usize) -> Vec<(u32, f64)> {
+        let mut similarities: Vec<(u32, f64)> = self
+            .dataset
+            .movies
+            .keys()
+            .filter(|&&id| id != movie_id)
+            .map(|&id| (id, self.genre_similarity(movie_id, id)))
+            .filter(|(_, sim)| *sim > 0.0)
+            .collect();
+
+        similarities.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        similarities.truncate(n);
+        similarities
+    }
+}
@@ -0,0 +1,42 @@
+use std::collections::{HashMap, VecDeque};
+
+/// One exchange in a conversation, as relayed to the recommender for
+/// context-aware re-ranking. Deliberately minimal — just the piece of a
+/// chat turn this crate actually reads, rather than a full transcript.
+#[derive(Debug, Clone)]
+pub struct ConversationTurn {
+    pub user_input: String,
+}
+
+/// The conversation state a caller feeds into
+/// [`HybridRecommender::recommend_with_context`](crate::hybrid::HybridRecommender::recommend_with_context):
+/// free-form key/value hints (e.g. `liked_genres`, `disliked_titles`) plus
+/// every turn added so far, oldest first (`add_turn` does not cap the
+/// history).
+///
+/// This is a small, crate-local stand-in for a real chatbot's session
+/// state, carrying only what the recommender needs, so this crate has no
+/// dependency on an actual conversational-AI crate.
+#[derive(Debug, Clone, Default)]
+pub struct ConversationContext {
+    context_data: HashMap<String, String>,
+    pub conversation_history: VecDeque<ConversationTurn>,
+}
+
+impl ConversationContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_context(&mut self, key: String, value: String) {
+        self.context_data.insert(key, value);
+    }
+
+    pub fn get_context(&self, key: &str) -> Option<&String> {
+        self.context_data.get(key)
+    }
+
+    pub fn add_turn(&mut self, turn: ConversationTurn) {
+        self.conversation_history.push_back(turn);
+    }
+}
@@ -0,0 +1,301 @@
+use crate::collaborative_filtering::CollaborativeFilter;
+use crate::content_based::ContentBasedFilter;
+use crate::hybrid::{HybridRecommender, HybridStrategy};
+use crate::matrix_factorization::MatrixFactorization;
+use crate::models::{Dataset, Rating};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::collections::{HashMap, HashSet};
+
+/// A held-out rating counts as "relevant" for Precision@k/Recall@k/MAP@k
+/// once it's at least this good, the usual binarization for ranking
+/// metrics over explicit star ratings.
+const RELEVANCE_THRESHOLD: f64 = 4.0;
+
+/// Something that can be trained on a `Dataset` and queried like any other
+/// recommender in this crate, letting [`Evaluator`] run all of them through
+/// one code path.
+pub trait Recommender {
+    fn predict(&self, user_id: u32, movie_id: u32) -> f64;
+    fn recommend(&self, user_id: u32, n: usize) -> Vec<(u32, f64)>;
+}
+
+impl<'a> Recommender for CollaborativeFilter<'a> {
+    fn predict(&self, user_id: u32, movie_id: u32) -> f64 {
+        CollaborativeFilter::predict(self, user_id, movie_id)
+    }
+
+    fn recommend(&self, user_id: u32, n: usize) -> Vec<(u32, f64)> {
+        CollaborativeFilter::recommend(self, user_id, n)
+    }
+}
+
+impl<'a> Recommender for ContentBasedFilter<'a> {
+    fn predict(&self, user_id: u32, movie_id: u32) -> f64 {
+        ContentBasedFilter::predict(self, user_id, movie_id)
+    }
+
+    fn recommend(&self, user_id: u32, n: usize) -> Vec<(u32, f64)> {
+        ContentBasedFilter::recommend(self, user_id, n)
+    }
+}
+
+impl<'a> Recommender for MatrixFactorization<'a> {
+    fn predict(&self, user_id: u32, movie_id: u32) -> f64 {
+        MatrixFactorization::predict(self, user_id, movie_id)
+    }
+
+    fn recommend(&self, user_id: u32, n: usize) -> Vec<(u32, f64)> {
+        MatrixFactorization::recommend(self, user_id, n)
+    }
+}
+
+impl<'a> Recommender for HybridRecommender<'a> {
+    fn predict(&self, user_id: u32, movie_id: u32) -> f64 {
+        HybridRecommender::predict(self, user_id, movie_id)
+    }
+
+    /// Evaluated with the `Mixed` strategy, since the trait has no way to
+    /// pick a `HybridStrategy` per call.
+    fn recommend(&self, user_id: u32, n: usize) -> Vec<(u32, f64)> {
+        HybridRecommender::recommend(self, user_id, n, HybridStrategy::Mixed)
+    }
+}
+
+/// A dataset split into a training set (for fitting a recommender) and a
+/// held-out set of ratings (for measuring it).
+pub struct TrainTestSplit {
+    pub train: Dataset,
+    pub test: Vec<Rating>,
+}
+
+/// Hold out `test_fraction` of each user's ratings using a seeded shuffle,
+/// so the split is reproducible across runs and every user contributes to
+/// both sets whenever they have enough ratings to.
+pub fn split_dataset(dataset: &Dataset, test_fraction: f64, seed: u64) -> TrainTestSplit {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut by_user: HashMap<u32, Vec<Rating>> = HashMap::new();
+    for &rating in &dataset.ratings {
+        by_user.entry(rating.user_id).or_default().push(rating);
+    }
+
+    let mut train = Dataset::new();
+    for user in dataset.users.values() {
+        train.add_user(user.clone());
+    }
+    for movie in dataset.movies.values() {
+        train.add_movie(movie.clone());
+    }
+
+    let mut test = Vec::new();
+    for mut ratings in by_user.into_values() {
+        ratings.shuffle(&mut rng);
+        let n_test = ((ratings.len() as f64) * test_fraction).round() as usize;
+        let (held_out, kept) = ratings.split_at(n_test.min(ratings.len()));
+
+        test.extend_from_slice(held_out);
+        for &rating in kept {
+            train.add_rating(rating);
+        }
+    }
+
+    TrainTestSplit { train, test }
+}
+
+/// Root-mean-square error between a model's predictions and the true
+/// held-out ratings.
+pub fn rmse<R: Recommender>(model: &R, test: &[Rating]) -> f64 {
+    if test.is_empty() {
+        return 0.0;
+    }
+
+    let squared_error_sum: f64 = test
+        .iter()
+        .map(|r| (model.predict(r.user_id, r.movie_id) - r.rating).powi(2))
+        .sum();
+
+    (squared_error_sum / test.len() as f64).sqrt()
+}
+
+/// Top-n ranking quality: Precision@k, Recall@k, and MAP@k, averaged over
+/// every user with at least one relevant held-out rating.
+#[derive(Debug, Clone, Copy)]
+pub struct RankingMetrics {
+    pub precision_at_k: f64,
+    pub recall_at_k: f64,
+    pub map_at_k: f64,
+}
+
+pub fn ranking_metrics<R: Recommender>(model: &R, test: &[Rating], k: usize) -> RankingMetrics {
+    let mut relevant_by_user: HashMap<u32, HashSet<u32>> = HashMap::new();
+    for rating in test {
+        if rating.rating >= RELEVANCE_THRESHOLD {
+            relevant_by_user
+                .entry(rating.user_id)
+                .or_default()
+                .insert(rating.movie_id);
+        }
+    }
+
+    let mut precisions = Vec::new();
+    let mut recalls = Vec::new();
+    let mut average_precisions = Vec::new();
+
+    for (&user_id, relevant) in &relevant_by_user {
+        let recommended = model.recommend(user_id, k);
+        let hits = recommended
+            .iter()
+            .filter(|(movie_id, _)| relevant.contains(movie_id))
+            .count();
+
+        precisions.push(hits as f64 / k as f64);
+        recalls.push(hits as f64 / relevant.len() as f64);
+
+        let mut hits_so_far = 0;
+        let mut precision_sum = 0.0;
+        for (rank, (movie_id, _)) in recommended.iter().enumerate() {
+            if relevant.contains(movie_id) {
+                hits_so_far += 1;
+                precision_sum += hits_so_far as f64 / (rank + 1) as f64;
+            }
+        }
+        average_precisions.push(precision_sum / relevant.len().min(k) as f64);
+    }
+
+    RankingMetrics {
+        precision_at_k: mean(&precisions),
+        recall_at_k: mean(&recalls),
+        map_at_k: mean(&average_precisions),
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+/// A recommender's measured performance on one evaluation run.
+pub struct EvaluationResult {
+    pub name: String,
+    pub rmse: f64,
+    pub ranking: RankingMetrics,
+}
+
+/// Runs any [`Recommender`] through a reproducible train/test split and
+/// reports RMSE plus top-k ranking metrics, so similarity-formula or
+/// model changes can be judged quantitatively instead of by eyeballing
+/// demo output.
+pub struct Evaluator {
+    pub split: TrainTestSplit,
+    pub k: usize,
+}
+
+impl Evaluator {
+    pub fn new(dataset: &Dataset, test_fraction: f64, seed: u64, k: usize) -> Self {
+        Evaluator {
+            split: split_dataset(dataset, test_fraction, seed),
+            k,
+        }
+    }
+
+    pub fn evaluate<R: Recommender>(&self, name: &str, model: &R) -> EvaluationResult {
+        EvaluationResult {
+            name: name.to_string(),
+            rmse: rmse(model, &self.split.test),
+            ranking: ranking_metrics(model, &self.split.test, self.k),
+        }
+    }
+
+    pub fn print_comparison(&self, results: &[EvaluationResult]) {
+        println!(
+            "{:<32} {:>8} {:>12} {:>10} {:>8}",
+            "Recommender", "RMSE", "Precision@k", "Recall@k", "MAP@k"
+        );
+        for result in results {
+            println!(
+                "{:<32} {:>8.4} {:>12.4} {:>10.4} {:>8.4}",
+                result.name,
+                result.rmse,
+                result.ranking.precision_at_k,
+                result.ranking.recall_at_k,
+                result.ranking.map_at_k,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Movie, User};
+
+    fn clustered_dataset() -> Dataset {
+        let mut dataset = Dataset::new();
+        for user_id in 1..=6 {
+            dataset.add_user(User {
+                id: user_id,
+                name: format!("user{user_id}"),
+            });
+        }
+        for movie_id in 1..=10 {
+            dataset.add_movie(Movie {
+                id: movie_id,
+                title: format!("movie{movie_id}"),
+                genres: vec!["Drama".to_string()],
+                year: 2000,
+                director: String::new(),
+                actors: vec![],
+            });
+        }
+        for user_id in 1..=6 {
+            for movie_id in 1..=10 {
+                dataset.add_rating(Rating {
+                    user_id,
+                    movie_id,
+                    rating: if (movie_id + user_id) % 2 == 0 { 5.0 } else { 2.0 },
+                });
+            }
+        }
+        dataset
+    }
+
+    #[test]
+    fn test_split_dataset_holds_out_requested_fraction_per_user() {
+        let dataset = clustered_dataset();
+        let split = split_dataset(&dataset, 0.2, 42);
+
+        // Every user had 10 ratings; ~20% (2) should be held out, the rest trained on.
+        let held_out_for_user_1 = split.test.iter().filter(|r| r.user_id == 1).count();
+        assert_eq!(held_out_for_user_1, 2);
+        assert_eq!(split.train.ratings.len() + split.test.len(), dataset.ratings.len());
+    }
+
+    #[test]
+    fn test_rmse_is_zero_for_a_perfect_predictor() {
+        struct PerfectPredictor<'a>(&'a Dataset);
+        impl<'a> Recommender for PerfectPredictor<'a> {
+            fn predict(&self, user_id: u32, movie_id: u32) -> f64 {
+                self.0
+                    .ratings
+                    .iter()
+                    .find(|r| r.user_id == user_id && r.movie_id == movie_id)
+                    .map(|r| r.rating)
+                    .unwrap_or(0.0)
+            }
+            fn recommend(&self, _user_id: u32, _n: usize) -> Vec<(u32, f64)> {
+                Vec::new()
+            }
+        }
+
+        let dataset = clustered_dataset();
+        let split = split_dataset(&dataset, 0.2, 42);
+        let model = PerfectPredictor(&dataset);
+
+        assert_eq!(rmse(&model, &split.test), 0.0);
+    }
+}
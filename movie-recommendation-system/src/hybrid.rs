@@ -0,0 +1,210 @@
+use crate::collaborative_filtering::CollaborativeFilter;
+use crate::content_based::ContentBasedFilter;
+use crate::conversation_context::ConversationContext;
+use crate::models::Dataset;
+use std::collections::{HashMap, HashSet};
+
+/// How [`HybridRecommender::recommend`] combines collaborative and
+/// content-based scores.
+pub enum HybridStrategy {
+    /// Blend both methods' normalized scores by the given weights.
+    Weighted {
+        collaborative_weight: f64,
+        content_weight: f64,
+    },
+    /// Use collaborative filtering once the user has enough ratings to
+    /// support it, otherwise fall back to content-based (addresses the
+    /// classic CF cold-start problem).
+    Switching,
+    /// Interleave both methods' ranked candidates, deduplicated.
+    Mixed,
+}
+
+pub struct HybridRecommender<'a> {
+    dataset: &'a Dataset,
+    collaborative: CollaborativeFilter<'a>,
+    content: ContentBasedFilter<'a>,
+}
+
+impl<'a> HybridRecommender<'a> {
+    pub fn new(dataset: &'a Dataset) -> Self {
+        HybridRecommender {
+            dataset,
+            collaborative: CollaborativeFilter::new(dataset),
+            content: ContentBasedFilter::new(dataset),
+        }
+    }
+
+    /// Predict `user_id`'s rating for `movie_id` as the average of the
+    /// collaborative and content-based predictions.
+    pub fn predict(&self, user_id: u32, movie_id: u32) -> f64 {
+        (self.collaborative.predict(user_id, movie_id) + self.content.predict(user_id, movie_id))
+            / 2.0
+    }
+
+    pub fn recommend(&self, user_id: u32, n: usize, strategy: HybridStrategy) -> Vec<(u32, f64)> {
+        match strategy {
+            HybridStrategy::Weighted {
+                collaborative_weight,
+                content_weight,
+            } => self.recommend_weighted(user_id, n, collaborative_weight, content_weight),
+            HybridStrategy::Switching => self.recommend_switching(user_id, n),
+            HybridStrategy::Mixed => self.recommend_mixed(user_id, n),
+        }
+    }
+
+    /// Re-rank [`Self::recommend`]'s `Mixed`-strategy candidates using live
+    /// conversation context: boosts movies whose genres match the
+    /// `liked_genres` context key, drops anything named in
+    /// `disliked_titles` or recently mentioned in `conversation_history`,
+    /// and lets the latest turn's free-text input additionally bias
+    /// scoring toward matching genres (an ephemeral "prompt", analogous to
+    /// a search engine's recommend-context parameter).
+    pub fn recommend_with_context(
+        &self,
+        user_id: u32,
+        context: &ConversationContext,
+        n: usize,
+    ) -> Vec<(u32, f64)> {
+        let liked_genres: HashSet<String> = context
+            .get_context("liked_genres")
+            .map(|value| value.split(',').map(|g| g.trim().to_lowercase()).collect())
+            .unwrap_or_default();
+
+        let disliked_titles: HashSet<String> = context
+            .get_context("disliked_titles")
+            .map(|value| value.split(',').map(|t| t.trim().to_lowercase()).collect())
+            .unwrap_or_default();
+
+        let mentioned: Vec<String> = context
+            .conversation_history
+            .iter()
+            .map(|turn| turn.user_input.to_lowercase())
+            .collect();
+
+        let prompt = context
+            .conversation_history
+            .back()
+            .map(|turn| turn.user_input.to_lowercase())
+            .unwrap_or_default();
+
+        let candidates = self.recommend(user_id, self.dataset.movies.len(), HybridStrategy::Mixed);
+
+        let mut rescored: Vec<(u32, f64)> = candidates
+            .into_iter()
+            .filter_map(|(movie_id, score)| {
+                let movie = self.dataset.movies.get(&movie_id)?;
+                let title = movie.title.to_lowercase();
+
+                let is_suppressed = disliked_titles.contains(&title)
+                    || mentioned.iter().any(|turn| turn.contains(&title));
+                if is_suppressed {
+                    return None;
+                }
+
+                let liked_matches = movie
+                    .genres
+                    .iter()
+                    .filter(|genre| liked_genres.contains(&genre.to_lowercase()))
+                    .count();
+                let prompt_matches = movie
+                    .genres
+                    .iter()
+                    .filter(|genre| !prompt.is_empty() && prompt.contains(&genre.to_lowercase()))
+                    .count();
+
+                let boosted = score + liked_matches as f64 * 0.1 + prompt_matches as f64 * 0.05;
+                Some((movie_id, boosted))
+            })
+            .collect();
+
+        rescored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        rescored.truncate(n);
+        rescored
+    }
+
+    /// Blend collaborative and content scores for every candidate by a
+    /// weighted sum, normalizing each method's scores to `[0, 1]` first so
+    /// neither dominates purely from differing scales.
+    fn recommend_weighted(
+        &self,
+        user_id: u32,
+        n: usize,
+        collaborative_weight: f64,
+        content_weight: f64,
+    ) -> Vec<(u32, f64)> {
+        let collaborative_scores =
+            normalize(self.collaborative.recommend(user_id, self.dataset.movies.len()));
+        let content_scores = normalize(self.content.recommend(user_id, self.dataset.movies.len()));
+
+        let mut combined: HashMap<u32, f64> = HashMap::new();
+        for (movie_id, score) in collaborative_scores {
+            *combined.entry(movie_id).or_insert(0.0) += score * collaborative_weight;
+        }
+        for (movie_id, score) in content_scores {
+            *combined.entry(movie_id).or_insert(0.0) += score * content_weight;
+        }
+
+        let mut recommendations: Vec<(u32, f64)> = combined.into_iter().collect();
+        recommendations.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        recommendations.truncate(n);
+        recommendations
+    }
+
+    fn recommend_switching(&self, user_id: u32, n: usize) -> Vec<(u32, f64)> {
+        const MIN_RATINGS_FOR_COLLABORATIVE: usize = 3;
+
+        if self.dataset.get_user_ratings(user_id).len() >= MIN_RATINGS_FOR_COLLABORATIVE {
+            self.collaborative.recommend(user_id, n)
+        } else {
+            self.content.recommend(user_id, n)
+        }
+    }
+
+    /// Interleave both methods' ranked candidates, preserving each one's
+    /// relative order and skipping movies already added by the other.
+    fn recommend_mixed(&self, user_id: u32, n: usize) -> Vec<(u32, f64)> {
+        let collaborative = self.collaborative.recommend(user_id, n);
+        let content = self.content.recommend(user_id, n);
+
+        let mut seen = HashSet::new();
+        let mut merged = Vec::new();
+
+        for i in 0..collaborative.len().max(content.len()) {
+            if let Some(&(movie_id, score)) = collaborative.get(i) {
+                if seen.insert(movie_id) {
+                    merged.push((movie_id, score));
+                }
+            }
+            if let Some(&(movie_id, score)) = content.get(i) {
+                if seen.insert(movie_id) {
+                    merged.push((movie_id, score));
+                }
+            }
+        }
+
+        merged.truncate(n);
+        merged
+    }
+}
+
+/// Min-max normalize scores to `[0, 1]`, so they can be fairly combined
+/// with another method's differently-scaled scores.
+fn normalize(scores: Vec<(u32, f64)>) -> Vec<(u32, f64)> {
+    if scores.is_empty() {
+        return scores;
+    }
+
+    let max = scores.iter().map(|(_, s)| *s).fold(f64::MIN, f64::max);
+    let min = scores.iter().map(|(_, s)| *s).fold(f64::MAX, f64::min);
+    let range = max - min;
+
+    if range == 0.0 {
+        return scores.into_iter().map(|(id, _)| (id, 1.0)).collect();
+    }
+
+    scores
+        .into_iter()
+        .map(|(id, s)| (id, (s - min) / range))
+        .collect()
+}
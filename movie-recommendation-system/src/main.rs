@@ -1,12 +1,18 @@
 mod models;
 mod collaborative_filtering;
 mod content_based;
+mod conversation_context;
+mod evaluation;
 mod hybrid;
+mod matrix_factorization;
 mod sample_data;
 
 use collaborative_filtering::CollaborativeFilter;
 use content_based::ContentBasedFilter;
+use conversation_context::{ConversationContext, ConversationTurn};
+use evaluation::Evaluator;
 use hybrid::{HybridRecommender, HybridStrategy};
+use matrix_factorization::MatrixFactorization;
 use sample_data::create_sample_dataset;
 
 fn main() {
@@ -77,6 +83,41 @@ fn main() {
     let similar_movies = content_filter.find_similar_movies(3, 5);
     display_recommendations(&dataset, &similar_movies);
 
+    // 8. Matrix Factorization (model-based collaborative filtering)
+    println!("\n--- Matrix Factorization (SGD) ---");
+    let matrix_factorization = MatrixFactorization::new(&dataset);
+    let mf_recs = matrix_factorization.recommend(test_user_id, 5);
+    display_recommendations(&dataset, &mf_recs);
+
+    // 9. Hybrid Recommender steered by live conversation context
+    println!("\n--- Hybrid Recommender (Conversation-Aware) ---");
+    let mut chat_context = ConversationContext::new();
+    chat_context.set_context("liked_genres".to_string(), "Sci-Fi, Thriller".to_string());
+    chat_context.add_turn(ConversationTurn {
+        user_input: "Something like Inception but not The Matrix".to_string(),
+    });
+    chat_context.set_context("disliked_titles".to_string(), "The Matrix".to_string());
+    let context_recs = hybrid.recommend_with_context(test_user_id, &chat_context, 5);
+    display_recommendations(&dataset, &context_recs);
+
+    // 10. Offline evaluation: RMSE and ranking metrics over a held-out split
+    println!("\n--- Offline Evaluation (20% held-out, k=5) ---");
+    let evaluator = Evaluator::new(&dataset, 0.2, 42, 5);
+    let train = &evaluator.split.train;
+
+    let eval_collab = CollaborativeFilter::new(train);
+    let eval_content = ContentBasedFilter::new(train);
+    let eval_mf = MatrixFactorization::new(train);
+    let eval_hybrid = HybridRecommender::new(train);
+
+    let results = vec![
+        evaluator.evaluate("Collaborative Filtering", &eval_collab),
+        evaluator.evaluate("Content-Based Filtering", &eval_content),
+        evaluator.evaluate("Matrix Factorization", &eval_mf),
+        evaluator.evaluate("Hybrid (Mixed)", &eval_hybrid),
+    ];
+    evaluator.print_comparison(&results);
+
     println!("\n=== Recommendation System Demo Complete ===");
 }
 
@@ -0,0 +1,437 @@
+use crate::models::Dataset;
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+
+/// Hyperparameters for [`MatrixFactorization`] training.
+pub struct MatrixFactorizationConfig {
+    pub n_factors: usize,
+    pub n_epochs: usize,
+    pub learning_rate: f64,
+    pub regularization: f64,
+}
+
+impl Default for MatrixFactorizationConfig {
+    fn default() -> Self {
+        Self {
+            n_factors: 20,
+            n_epochs: 50,
+            learning_rate: 0.005,
+            regularization: 0.02,
+        }
+    }
+}
+
+/// Model-based collaborative filtering via regularized SGD matrix
+/// factorization: r̂_ui = μ + b_u + b_i + P_u · Q_i.
+///
+/// Unlike [`crate::collaborative_filtering::CollaborativeFilter`], which
+/// recomputes user similarity on every call, this learns a fixed set
+/// of latent factors up front and generalizes to sparse ratings better.
+pub struct MatrixFactorization<'a> {
+    dataset: &'a Dataset,
+    config: MatrixFactorizationConfig,
+    global_mean: f64,
+    user_bias: HashMap<u32, f64>,
+    movie_bias: HashMap<u32, f64>,
+    user_factors: HashMap<u32, Vec<f64>>,
+    movie_factors: HashMap<u32, Vec<f64>>,
+}
+
+impl<'a> MatrixFactorization<'a> {
+    /// Build and train a model over `dataset` using the default
+    /// hyperparameters.
+    pub fn new(dataset: &'a Dataset) -> Self {
+        Self::with_config(dataset, MatrixFactorizationConfig::default())
+    }
+
+    /// Build and train a model over `dataset` using custom hyperparameters.
+    pub fn with_config(dataset: &'a Dataset, config: MatrixFactorizationConfig) -> Self {
+        let mut model = Self {
+            dataset,
+            config,
+            global_mean: 0.0,
+            user_bias: HashMap::new(),
+            movie_bias: HashMap::new(),
+            user_factors: HashMap::new(),
+            movie_factors: HashMap::new(),
+        };
+        model.train();
+        model
+    }
+
+    /// Train on implicit feedback (views, purchases, watch counts, ...)
+    /// with weighted alternating least squares, following Hu, Koren &
+    /// Volinsky (2008): each observed `(user, movie)` rating value `r_ui`
+    /// becomes a binary preference `p_ui = 1` with confidence
+    /// `c_ui = 1 + alpha * r_ui`, and the factors minimize
+    /// `Σ c_ui·(p_ui − x_u·y_i)² + lambda·(‖x_u‖² + ‖y_i‖²)`.
+    ///
+    /// Unlike [`Self::new`]/[`Self::with_config`], this has no bias terms
+    /// or global mean — `predict`/`recommend` fall back to a pure dot
+    /// product, which is the right score for a preference model.
+    pub fn fit_implicit(
+        dataset: &'a Dataset,
+        factors: usize,
+        iterations: usize,
+        alpha: f64,
+        lambda: f64,
+    ) -> Self {
+        let mut model = Self {
+            dataset,
+            config: MatrixFactorizationConfig {
+                n_factors: factors,
+                n_epochs: iterations,
+                learning_rate: 0.0,
+                regularization: lambda,
+            },
+            global_mean: 0.0,
+            user_bias: HashMap::new(),
+            movie_bias: HashMap::new(),
+            user_factors: HashMap::new(),
+            movie_factors: HashMap::new(),
+        };
+        model.train_implicit(factors, iterations, alpha, lambda);
+        model
+    }
+
+    fn train_implicit(&mut self, factors: usize, iterations: usize, alpha: f64, lambda: f64) {
+        if self.dataset.ratings.is_empty() {
+            return;
+        }
+
+        let mut rng = rand::thread_rng();
+        let user_ids: Vec<u32> = self.dataset.users.keys().copied().collect();
+        let movie_ids: Vec<u32> = self.dataset.movies.keys().copied().collect();
+
+        for &user_id in &user_ids {
+            self.user_factors
+                .insert(user_id, random_factors(&mut rng, factors));
+        }
+        for &movie_id in &movie_ids {
+            self.movie_factors
+                .insert(movie_id, random_factors(&mut rng, factors));
+        }
+
+        // Group each side's observations with their confidence, so every
+        // per-user/per-item solve only sums over what it actually interacted
+        // with rather than the whole matrix.
+        let mut user_interactions: HashMap<u32, Vec<(u32, f64)>> = HashMap::new();
+        let mut movie_interactions: HashMap<u32, Vec<(u32, f64)>> = HashMap::new();
+        for rating in &self.dataset.ratings {
+            let confidence = 1.0 + alpha * rating.rating;
+            user_interactions
+                .entry(rating.user_id)
+                .or_default()
+                .push((rating.movie_id, confidence));
+            movie_interactions
+                .entry(rating.movie_id)
+                .or_default()
+                .push((rating.user_id, confidence));
+        }
+
+        for _ in 0..iterations {
+            // Fix item factors Y, solve for every user's x_u. YᵀY is shared
+            // across all users this half-iteration.
+            let yty = gram_matrix(movie_ids.iter().map(|id| &self.movie_factors[id]), factors);
+            for &user_id in &user_ids {
+                let empty = Vec::new();
+                let interactions = user_interactions.get(&user_id).unwrap_or(&empty);
+                let x_u = solve_als_row(&yty, &self.movie_factors, interactions, factors, lambda);
+                self.user_factors.insert(user_id, x_u);
+            }
+
+            // Fix user factors X, solve for every item's y_i.
+            let xtx = gram_matrix(user_ids.iter().map(|id| &self.user_factors[id]), factors);
+            for &movie_id in &movie_ids {
+                let empty = Vec::new();
+                let interactions = movie_interactions.get(&movie_id).unwrap_or(&empty);
+                let y_i = solve_als_row(&xtx, &self.user_factors, interactions, factors, lambda);
+                self.movie_factors.insert(movie_id, y_i);
+            }
+        }
+    }
+
+    fn train(&mut self) {
+        if self.dataset.ratings.is_empty() {
+            return;
+        }
+
+        self.global_mean = self.dataset.ratings.iter().map(|r| r.rating).sum::<f64>()
+            / self.dataset.ratings.len() as f64;
+
+        let mut rng = rand::thread_rng();
+        for &user_id in self.dataset.users.keys() {
+            self.user_bias.insert(user_id, 0.0);
+            self.user_factors
+                .insert(user_id, random_factors(&mut rng, self.config.n_factors));
+        }
+        for &movie_id in self.dataset.movies.keys() {
+            self.movie_bias.insert(movie_id, 0.0);
+            self.movie_factors
+                .insert(movie_id, random_factors(&mut rng, self.config.n_factors));
+        }
+
+        let lr = self.config.learning_rate;
+        let lambda = self.config.regularization;
+
+        for _ in 0..self.config.n_epochs {
+            for rating in &self.dataset.ratings {
+                let error = rating.rating - self.predict(rating.user_id, rating.movie_id);
+
+                let user_bias = self.user_bias[&rating.user_id];
+                let movie_bias = self.movie_bias[&rating.movie_id];
+                self.user_bias
+                    .insert(rating.user_id, user_bias + lr * (error - lambda * user_bias));
+                self.movie_bias
+                    .insert(rating.movie_id, movie_bias + lr * (error - lambda * movie_bias));
+
+                let p_u = self.user_factors[&rating.user_id].clone();
+                let q_i = self.movie_factors[&rating.movie_id].clone();
+
+                let new_p_u: Vec<f64> = p_u
+                    .iter()
+                    .zip(&q_i)
+                    .map(|(p, q)| p + lr * (error * q - lambda * p))
+                    .collect();
+                let new_q_i: Vec<f64> = q_i
+                    .iter()
+                    .zip(&p_u)
+                    .map(|(q, p)| q + lr * (error * p - lambda * q))
+                    .collect();
+
+                self.user_factors.insert(rating.user_id, new_p_u);
+                self.movie_factors.insert(rating.movie_id, new_q_i);
+            }
+        }
+    }
+
+    /// Predict `user_id`'s rating for `movie_id`. Falls back to the global
+    /// mean (plus whichever bias is known) for a user or movie not seen
+    /// during training.
+    pub fn predict(&self, user_id: u32, movie_id: u32) -> f64 {
+        let user_bias = self.user_bias.get(&user_id).copied().unwrap_or(0.0);
+        let movie_bias = self.movie_bias.get(&movie_id).copied().unwrap_or(0.0);
+
+        let dot = match (
+            self.user_factors.get(&user_id),
+            self.movie_factors.get(&movie_id),
+        ) {
+            (Some(p_u), Some(q_i)) => p_u.iter().zip(q_i).map(|(p, q)| p * q).sum(),
+            _ => 0.0,
+        };
+
+        self.global_mean + user_bias + movie_bias + dot
+    }
+
+    /// Rank every movie `user_id` hasn't rated by predicted score and
+    /// return the top `n`, matching the `(movie_id, score)` shape used by
+    /// [`crate::collaborative_filtering::CollaborativeFilter`] so it slots
+    /// into `display_recommendations` and `HybridRecommender` unchanged.
+    pub fn recommend(&self, user_id: u32, n: usize) -> Vec<(u32, f64)> {
+        let rated: HashSet<u32> = self
+            .dataset
+            .get_user_ratings(user_id)
+            .iter()
+            .map(|r| r.movie_id)
+            .collect();
+
+        let mut scored: Vec<(u32, f64)> = self
+            .dataset
+            .movies
+            .keys()
+            .filter(|movie_id| !rated.contains(movie_id))
+            .map(|&movie_id| (movie_id, self.predict(user_id, movie_id)))
+            .collect();
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        scored.truncate(n);
+        scored
+    }
+}
+
+/// Small random initial values, so latent factors start near zero but
+/// break symmetry between users/movies.
+fn random_factors(rng: &mut impl Rng, n_factors: usize) -> Vec<f64> {
+    (0..n_factors).map(|_| rng.gen_range(-0.1..0.1)).collect()
+}
+
+/// `Σ v·vᵀ` over `vectors`, an `f × f` Gram matrix (`YᵀY` or `XᵀX`).
+fn gram_matrix<'v>(vectors: impl Iterator<Item = &'v Vec<f64>>, f: usize) -> Vec<Vec<f64>> {
+    let mut gram = vec![vec![0.0; f]; f];
+    for v in vectors {
+        for i in 0..f {
+            for j in 0..f {
+                gram[i][j] += v[i] * v[j];
+            }
+        }
+    }
+    gram
+}
+
+/// Solve one side of the weighted-ALS normal equations for a single
+/// user/item row: `(gram + λI + Σ(c−1)·v·vᵀ)⁻¹ · Σ c·v`, where `gram` is
+/// the shared `YᵀY`/`XᵀX` and the sums run only over `interactions`
+/// (the Hu–Koren–Volinsky trick that keeps each solve cheap).
+fn solve_als_row(
+    gram: &[Vec<f64>],
+    factors: &HashMap<u32, Vec<f64>>,
+    interactions: &[(u32, f64)],
+    f: usize,
+    lambda: f64,
+) -> Vec<f64> {
+    let mut a = gram.to_vec();
+    for i in 0..f {
+        a[i][i] += lambda;
+    }
+
+    let mut b = vec![0.0; f];
+    for &(id, confidence) in interactions {
+        let v = &factors[&id];
+        for i in 0..f {
+            for j in 0..f {
+                a[i][j] += (confidence - 1.0) * v[i] * v[j];
+            }
+            b[i] += confidence * v[i];
+        }
+    }
+
+    solve_linear_system(&a, &b)
+}
+
+/// Solve the `n × n` system `a·x = b` via Gaussian elimination with partial
+/// pivoting. Treats a near-zero pivot as singular and leaves that
+/// coordinate at `0.0` rather than dividing by it.
+fn solve_linear_system(a: &[Vec<f64>], b: &[f64]) -> Vec<f64> {
+    let n = b.len();
+    let mut aug: Vec<Vec<f64>> = (0..n)
+        .map(|i| {
+            let mut row = a[i].clone();
+            row.push(b[i]);
+            row
+        })
+        .collect();
+
+    for col in 0..n {
+        let pivot_row = (col..n)
+            .max_by(|&r1, &r2| aug[r1][col].abs().partial_cmp(&aug[r2][col].abs()).unwrap())
+            .unwrap();
+        aug.swap(col, pivot_row);
+
+        let pivot = aug[col][col];
+        if pivot.abs() < 1e-12 {
+            continue;
+        }
+        for row in (col + 1)..n {
+            let factor = aug[row][col] / pivot;
+            for k in col..=n {
+                aug[row][k] -= factor * aug[col][k];
+            }
+        }
+    }
+
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let mut sum = aug[row][n];
+        for k in (row + 1)..n {
+            sum -= aug[row][k] * x[k];
+        }
+        x[row] = if aug[row][row].abs() < 1e-12 {
+            0.0
+        } else {
+            sum / aug[row][row]
+        };
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Movie, Rating, User};
+
+    fn clustered_dataset() -> Dataset {
+        let mut dataset = Dataset::new();
+        for user_id in 1..=6 {
+            dataset.add_user(User {
+                id: user_id,
+                name: format!("user{user_id}"),
+            });
+        }
+        for movie_id in 1..=6 {
+            dataset.add_movie(Movie {
+                id: movie_id,
+                title: format!("movie{movie_id}"),
+                genres: vec![],
+                year: 2000,
+                director: String::new(),
+                actors: vec![],
+            });
+        }
+
+        // Two taste clusters with no overlap, so a low-rank factorization
+        // should fit them well.
+        for user_id in [1, 2, 3] {
+            for movie_id in [1, 2, 3] {
+                dataset.add_rating(Rating {
+                    user_id,
+                    movie_id,
+                    rating: 5.0,
+                });
+            }
+        }
+        for user_id in [4, 5, 6] {
+            for movie_id in [4, 5, 6] {
+                dataset.add_rating(Rating {
+                    user_id,
+                    movie_id,
+                    rating: 1.0,
+                });
+            }
+        }
+
+        dataset
+    }
+
+    #[test]
+    fn test_reconstructs_held_out_rating_within_tolerance() {
+        let mut dataset = clustered_dataset();
+        dataset
+            .ratings
+            .retain(|r| !(r.user_id == 1 && r.movie_id == 3));
+
+        let model = MatrixFactorization::new(&dataset);
+        let predicted = model.predict(1, 3);
+
+        assert!(
+            (predicted - 5.0).abs() < 1.0,
+            "predicted {predicted} too far from held-out rating 5.0"
+        );
+    }
+
+    #[test]
+    fn test_recommend_excludes_already_rated_movies() {
+        let dataset = clustered_dataset();
+        let model = MatrixFactorization::new(&dataset);
+
+        let recommendations = model.recommend(1, 3);
+        assert!(recommendations
+            .iter()
+            .all(|(movie_id, _)| ![1, 2, 3].contains(movie_id)));
+    }
+
+    #[test]
+    fn test_fit_implicit_scores_interacted_items_higher() {
+        let dataset = clustered_dataset();
+        let model = MatrixFactorization::fit_implicit(&dataset, 8, 20, 40.0, 0.1);
+
+        // User 1 interacted with movies 1-3 but not 4-6; the learned
+        // preference score should reflect that even with no bias terms.
+        let interacted_score = model.predict(1, 1);
+        let uninteracted_score = model.predict(1, 4);
+
+        assert!(
+            interacted_score > uninteracted_score,
+            "interacted score {interacted_score} should exceed uninteracted score {uninteracted_score}"
+        );
+    }
+}
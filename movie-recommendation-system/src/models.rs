@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct User {
+    pub id: u32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Movie {
+    pub id: u32,
+    pub title: String,
+    pub genres: Vec<String>,
+    pub year: u32,
+    pub director: String,
+    pub actors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Rating {
+    pub user_id: u32,
+    pub movie_id: u32,
+    pub rating: f64,
+}
+
+/// The users, movies, and ratings that every recommender in this crate is
+/// built from.
+#[derive(Debug, Default)]
+pub struct Dataset {
+    pub users: HashMap<u32, User>,
+    pub movies: HashMap<u32, Movie>,
+    pub ratings: Vec<Rating>,
+}
+
+impl Dataset {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_user(&mut self, user: User) {
+        self.users.insert(user.id, user);
+    }
+
+    pub fn add_movie(&mut self, movie: Movie) {
+        self.movies.insert(movie.id, movie);
+    }
+
+    pub fn add_rating(&mut self, rating: Rating) {
+        self.ratings.push(rating);
+    }
+
+    pub fn get_user_ratings(&self, user_id: u32) -> Vec<&Rating> {
+        self.ratings.iter().filter(|r| r.user_id == user_id).collect()
+    }
+
+    pub fn get_movie_ratings(&self, movie_id: u32) -> Vec<&Rating> {
+        self.ratings.iter().filter(|r| r.movie_id == movie_id).collect()
+    }
+
+    /// The mean rating across the whole dataset, used as a fallback
+    /// prediction when a recommender has no signal for a user/movie pair.
+    pub fn mean_rating(&self) -> f64 {
+        if self.ratings.is_empty() {
+            return 0.0;
+        }
+        self.ratings.iter().map(|r| r.rating).sum::<f64>() / self.ratings.len() as f64
+    }
+}